@@ -0,0 +1,553 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use common::trace;
+
+#[derive(Debug, PartialEq)]
+pub enum Target {
+    All,
+    Day(u8),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub target: Target,
+    pub input: Option<String>,
+    pub json: bool,
+    pub example: bool,
+    pub render: Option<String>,
+    // how many days to run at once, one per core when not given
+    pub jobs: Option<usize>,
+    // how long each part may run before it's reported as a TIMEOUT
+    pub timeout: Option<Duration>,
+    pub config: Option<String>,
+    // key=value or day.key=value parameter overrides, applied over the config file
+    pub set: Vec<String>,
+    // where to write each simulation step, and which steps
+    pub trace: Option<String>,
+    pub trace_steps: Option<Range<u64>>,
+    // solve again instead of using answers from the cache
+    pub no_cache: bool,
+}
+
+// every day verifies within seconds even in a debug build, so this only stops runaways
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+impl RunArgs {
+    pub fn parse(args: &[String]) -> Result<RunArgs, String> {
+        let mut target = None;
+        let mut input = None;
+        let mut json = false;
+        let mut example = false;
+        let mut render = None;
+        let mut jobs = None;
+        let mut timeout = None;
+        let mut config = None;
+        let mut set = vec![];
+        let mut trace = None;
+        let mut trace_steps = None;
+        let mut no_cache = false;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => match args.next() {
+                    Some(path) => input = Some(path.clone()),
+                    None => return Err(String::from("--input needs a path, or - for stdin")),
+                },
+                "--json" => json = true,
+                "--example" => example = true,
+                "--render" => match args.next() {
+                    Some(path) => render = Some(path.clone()),
+                    None => return Err(String::from("--render needs a path ending in .ppm, .pgm or .svg")),
+                },
+                "--jobs" => match args.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(value)) if value > 0 => jobs = Some(value),
+                    _ => return Err(String::from("--jobs needs a number greater than 0")),
+                },
+                "--timeout" => match args.next().map(|value| value.parse::<f64>()) {
+                    Some(Ok(seconds)) if seconds > 0.0 && seconds.is_finite() => {
+                        timeout = Some(Duration::from_secs_f64(seconds))
+                    }
+                    _ => return Err(String::from("--timeout needs a number of seconds greater than 0")),
+                },
+                "--config" => match args.next() {
+                    Some(path) => config = Some(path.clone()),
+                    None => return Err(String::from("--config needs a path")),
+                },
+                "--set" => match args.next() {
+                    Some(setting) => set.push(setting.clone()),
+                    None => return Err(String::from("--set needs a key=value or day.key=value")),
+                },
+                "--trace" => match args.next() {
+                    Some(path) => trace = Some(path.clone()),
+                    None => return Err(String::from("--trace needs a path")),
+                },
+                "--trace-steps" => match args.next() {
+                    Some(range) => trace_steps = Some(trace::parse_steps(range)?),
+                    None => return Err(String::from("--trace-steps needs a range like 10..20")),
+                },
+                "--no-cache" => no_cache = true,
+                _ if target.is_none() => target = Some(parse_target(arg)?),
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        let target = match target {
+            Some(target) => target,
+            None => return Err(String::from("missing which day to run")),
+        };
+
+        // one input file can't be shared by every day
+        if target == Target::All && (input.is_some() || render.is_some() || trace.is_some()) {
+            return Err(String::from("--input, --render and --trace can only be used with a single day"));
+        }
+
+        if trace_steps.is_some() && trace.is_none() {
+            return Err(String::from("--trace-steps needs a --trace to write to"));
+        }
+
+        // examples bring their own parameters
+        if example && (input.is_some() || json || render.is_some() || config.is_some() || !set.is_empty() || trace.is_some()) {
+            return Err(String::from("--example can't be combined with --input, --json, --render, --config, --set or --trace"));
+        }
+
+        return Ok(RunArgs { target, input, json, example, render, jobs, timeout, config, set, trace, trace_steps, no_cache });
+    }
+}
+
+pub const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub run: RunArgs,
+    pub runs: usize,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+}
+
+impl BenchArgs {
+    // pulls out the benchmark only flags and leaves the rest to RunArgs
+    pub fn parse(args: &[String]) -> Result<BenchArgs, String> {
+        let mut runs = DEFAULT_RUNS;
+        let mut save_baseline = None;
+        let mut baseline = None;
+        let mut rest = vec![];
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runs" => match args.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(value)) if value > 0 => runs = value,
+                    _ => return Err(String::from("--runs needs a number greater than 0")),
+                },
+                "--save-baseline" => match args.next() {
+                    Some(path) => save_baseline = Some(path.clone()),
+                    None => return Err(String::from("--save-baseline needs a path")),
+                },
+                "--baseline" => match args.next() {
+                    Some(path) => baseline = Some(path.clone()),
+                    None => return Err(String::from("--baseline needs a path")),
+                },
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        let run = RunArgs::parse(&rest)?;
+        if run.json || run.example || run.render.is_some() || run.trace.is_some() {
+            return Err(String::from("--json, --example, --render and --trace are only supported by run"));
+        }
+        // every run is timed for real anyway
+        if run.no_cache {
+            return Err(String::from("bench never uses the cache, so --no-cache isn't needed"));
+        }
+
+        // days timed side by side would slow each other down, and a timeout would cut the timing short
+        if run.jobs.is_some() || run.timeout.is_some() {
+            return Err(String::from("--jobs and --timeout are only supported by run and verify"));
+        }
+
+        return Ok(BenchArgs { run, runs, save_baseline, baseline });
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BatchArgs {
+    pub run: RunArgs,
+    // every file in here is one input for the day
+    pub dir: String,
+    pub csv: bool,
+}
+
+impl BatchArgs {
+    // pulls out the batch only flags and leaves the rest to RunArgs
+    pub fn parse(args: &[String]) -> Result<BatchArgs, String> {
+        let mut dir = None;
+        let mut csv = false;
+        let mut rest = vec![];
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dir" => match args.next() {
+                    Some(path) => dir = Some(path.clone()),
+                    None => return Err(String::from("--dir needs a directory of inputs")),
+                },
+                "--csv" => csv = true,
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        let run = RunArgs::parse(&rest)?;
+        if run.target == Target::All {
+            return Err(String::from("batch works on one day at a time"));
+        }
+        if run.input.is_some() || run.example || run.render.is_some() || run.trace.is_some() {
+            return Err(String::from("--input, --example, --render and --trace aren't supported by batch"));
+        }
+        if run.json && csv {
+            return Err(String::from("pick one of --json or --csv"));
+        }
+
+        match dir {
+            Some(dir) => Ok(BatchArgs { run, dir, csv }),
+            None => Err(String::from("missing --dir with the inputs to solve")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct StepArgs {
+    pub run: RunArgs,
+    // a snapshot saved with `w` to carry on from
+    pub resume: Option<String>,
+}
+
+impl StepArgs {
+    // pulls out the step only flags and leaves the rest to RunArgs
+    pub fn parse(args: &[String]) -> Result<StepArgs, String> {
+        let mut resume = None;
+        let mut rest = vec![];
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--resume" => match args.next() {
+                    Some(path) => resume = Some(path.clone()),
+                    None => return Err(String::from("--resume needs the path of a snapshot")),
+                },
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        let run = RunArgs::parse(&rest)?;
+
+        if run.target == Target::All {
+            return Err(String::from("step works on one day at a time"));
+        }
+        if run.json || run.example || run.render.is_some() || run.trace.is_some() || run.jobs.is_some() || run.timeout.is_some() || run.no_cache {
+            return Err(String::from("step only takes --input, --config, --set and --resume"));
+        }
+        // stdin is where the commands come from
+        if run.input.as_deref() == Some("-") {
+            return Err(String::from("step can't read its input from stdin, use a path"));
+        }
+
+        return Ok(StepArgs { run, resume });
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateArgs {
+    pub day: u8,
+    pub seed: Option<u64>,
+    pub size: Option<usize>,
+}
+
+impl GenerateArgs {
+    pub fn parse(args: &[String]) -> Result<GenerateArgs, String> {
+        let mut day = None;
+        let mut seed = None;
+        let mut size = None;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => match args.next().map(|value| value.parse::<u64>()) {
+                    Some(Ok(value)) => seed = Some(value),
+                    _ => return Err(String::from("--seed needs a number")),
+                },
+                "--size" => match args.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(value)) => size = Some(value),
+                    _ => return Err(String::from("--size needs a number")),
+                },
+                _ if day.is_none() => match parse_target(arg)? {
+                    Target::Day(number) => day = Some(number),
+                    Target::All => return Err(String::from("generate works on one day at a time")),
+                },
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        match day {
+            Some(day) => Ok(GenerateArgs { day, seed, size }),
+            None => Err(String::from("missing which day to generate an input for")),
+        }
+    }
+}
+
+pub const DEFAULT_CASES: u64 = 100;
+
+#[derive(Debug, PartialEq)]
+pub struct DiffArgs {
+    pub target: Target,
+    pub seed: Option<u64>,
+    pub cases: u64,
+    pub size: Option<usize>,
+}
+
+impl DiffArgs {
+    pub fn parse(args: &[String]) -> Result<DiffArgs, String> {
+        let mut target = None;
+        let mut seed = None;
+        let mut cases = DEFAULT_CASES;
+        let mut size = None;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => match args.next().map(|value| value.parse::<u64>()) {
+                    Some(Ok(value)) => seed = Some(value),
+                    _ => return Err(String::from("--seed needs a number")),
+                },
+                "--cases" => match args.next().map(|value| value.parse::<u64>()) {
+                    Some(Ok(value)) if value > 0 => cases = value,
+                    _ => return Err(String::from("--cases needs a number greater than 0")),
+                },
+                "--size" => match args.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(value)) if value > 0 => size = Some(value),
+                    _ => return Err(String::from("--size needs a number greater than 0")),
+                },
+                _ if target.is_none() => target = Some(parse_target(arg)?),
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        match target {
+            Some(target) => Ok(DiffArgs { target, seed, cases, size }),
+            None => Err(String::from("missing which day to compare")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LintArgs {
+    pub target: Target,
+    pub input: Option<String>,
+    // rewrite the input with its formatting problems fixed
+    pub fix: bool,
+}
+
+impl LintArgs {
+    pub fn parse(args: &[String]) -> Result<LintArgs, String> {
+        let mut target = None;
+        let mut input = None;
+        let mut fix = false;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => match args.next() {
+                    Some(path) => input = Some(path.clone()),
+                    None => return Err(String::from("--input needs a path, or - for stdin")),
+                },
+                "--fix" => fix = true,
+                _ if target.is_none() => target = Some(parse_target(arg)?),
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        let target = target.unwrap_or(Target::All);
+
+        if target == Target::All && input.is_some() {
+            return Err(String::from("--input can only be used with a single day"));
+        }
+        if fix && input.as_deref() == Some("-") {
+            return Err(String::from("--fix rewrites the input file, so it can't be used with stdin"));
+        }
+
+        return Ok(LintArgs { target, input, fix });
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CacheArgs {
+    Show,
+    // only the entries an older build saved when `old` is set
+    Clear { old: bool },
+}
+
+impl CacheArgs {
+    pub fn parse(args: &[String]) -> Result<CacheArgs, String> {
+        let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+
+        match args.as_slice() {
+            [] | ["show"] => Ok(CacheArgs::Show),
+            ["clear"] => Ok(CacheArgs::Clear { old: false }),
+            ["clear", "--old"] => Ok(CacheArgs::Clear { old: true }),
+            ["show" | "clear", arg, ..] => Err(format!("unexpected argument: {}", arg)),
+            [command, ..] => Err(format!("expected show or clear, got: {}", command)),
+        }
+    }
+}
+
+fn parse_target(arg: &str) -> Result<Target, String> {
+    if arg == "all" {
+        return Ok(Target::All);
+    }
+
+    match arg.parse::<u8>() {
+        Ok(day) => Ok(Target::Day(day)),
+        Err(_) => Err(format!("expected a day number or all, got: {}", arg)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        return values.iter().map(|value| value.to_string()).collect();
+    }
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(
+            RunArgs::parse(&args(&["all"])),
+            Ok(RunArgs { target: Target::All, input: None, json: false, example: false, render: None, jobs: None, timeout: None, config: None, set: vec![], trace: None, trace_steps: None, no_cache: false })
+        );
+        assert_eq!(
+            RunArgs::parse(&args(&["7", "--input", "-", "--json"])),
+            Ok(RunArgs { target: Target::Day(7), input: Some(String::from("-")), json: true, example: false, render: None, jobs: None, timeout: None, config: None, set: vec![], trace: None, trace_steps: None, no_cache: false })
+        );
+        assert!(RunArgs::parse(&args(&[])).is_err());
+        assert!(RunArgs::parse(&args(&["seven"])).is_err());
+        assert!(RunArgs::parse(&args(&["all", "--input", "day-7.txt"])).is_err());
+        assert!(RunArgs::parse(&args(&["all", "--example"])).unwrap().example);
+        assert!(RunArgs::parse(&args(&["7", "--example", "--input", "-"])).is_err());
+        assert_eq!(RunArgs::parse(&args(&["6", "--render", "regions.svg"])).unwrap().render, Some(String::from("regions.svg")));
+        assert!(RunArgs::parse(&args(&["all", "--render", "regions.svg"])).is_err());
+        assert_eq!(RunArgs::parse(&args(&["all", "--jobs", "2"])).unwrap().jobs, Some(2));
+        assert!(RunArgs::parse(&args(&["all", "--jobs", "0"])).is_err());
+        assert_eq!(RunArgs::parse(&args(&["9", "--timeout", "0.5"])).unwrap().timeout, Some(Duration::from_millis(500)));
+        assert!(RunArgs::parse(&args(&["9", "--timeout", "0"])).is_err());
+        assert_eq!(
+            RunArgs::parse(&args(&["all", "--config", "team.toml", "--set", "7.workers=2"])).unwrap().set,
+            vec![String::from("7.workers=2")]
+        );
+        assert!(RunArgs::parse(&args(&["7", "--example", "--set", "workers=2"])).is_err());
+    }
+
+    #[test]
+    fn step_parse_works() {
+        assert_eq!(StepArgs::parse(&args(&["12", "--set", "generations=500"])).unwrap().run.set, vec![String::from("generations=500")]);
+        assert!(StepArgs::parse(&args(&["all"])).is_err());
+        assert!(StepArgs::parse(&args(&["12", "--input", "-"])).is_err());
+        assert!(StepArgs::parse(&args(&["12", "--json"])).is_err());
+        assert!(StepArgs::parse(&args(&["12", "--trace", "pots.jsonl"])).is_err());
+        assert_eq!(StepArgs::parse(&args(&["9", "--resume", "turn-4600.snapshot"])).unwrap().resume, Some(String::from("turn-4600.snapshot")));
+        assert!(StepArgs::parse(&args(&["9", "--resume"])).is_err());
+    }
+
+    #[test]
+    fn trace_parse_works() {
+        let run_args = RunArgs::parse(&args(&["12", "--trace", "pots.jsonl", "--trace-steps", "10..20"])).unwrap();
+        assert_eq!((run_args.trace, run_args.trace_steps), (Some(String::from("pots.jsonl")), Some(10..20)));
+
+        assert_eq!(RunArgs::parse(&args(&["12", "--trace", "pots.jsonl"])).unwrap().trace_steps, None);
+        assert!(RunArgs::parse(&args(&["all", "--trace", "pots.jsonl"])).is_err());
+        assert!(RunArgs::parse(&args(&["12", "--trace-steps", "10..20"])).is_err());
+        assert!(RunArgs::parse(&args(&["12", "--trace", "pots.jsonl", "--trace-steps", "20"])).is_err());
+        assert!(RunArgs::parse(&args(&["12", "--example", "--trace", "pots.jsonl"])).is_err());
+        assert!(BenchArgs::parse(&args(&["12", "--trace", "pots.jsonl"])).is_err());
+        assert!(BatchArgs::parse(&args(&["12", "--dir", "inputs", "--trace", "pots.jsonl"])).is_err());
+    }
+
+    #[test]
+    fn cache_parse_works() {
+        assert!(RunArgs::parse(&args(&["all", "--no-cache"])).unwrap().no_cache);
+        assert!(BenchArgs::parse(&args(&["all", "--no-cache"])).is_err());
+        assert!(StepArgs::parse(&args(&["12", "--no-cache"])).is_err());
+
+        assert_eq!(CacheArgs::parse(&args(&[])), Ok(CacheArgs::Show));
+        assert_eq!(CacheArgs::parse(&args(&["show"])), Ok(CacheArgs::Show));
+        assert_eq!(CacheArgs::parse(&args(&["clear", "--old"])), Ok(CacheArgs::Clear { old: true }));
+        assert!(CacheArgs::parse(&args(&["show", "--old"])).is_err());
+        assert!(CacheArgs::parse(&args(&["empty"])).is_err());
+    }
+
+    #[test]
+    fn generate_parse_works() {
+        assert_eq!(
+            GenerateArgs::parse(&args(&["8", "--seed", "42", "--size", "5000"])),
+            Ok(GenerateArgs { day: 8, seed: Some(42), size: Some(5000) })
+        );
+        assert_eq!(GenerateArgs::parse(&args(&["8"])), Ok(GenerateArgs { day: 8, seed: None, size: None }));
+        assert!(GenerateArgs::parse(&args(&["all"])).is_err());
+        assert!(GenerateArgs::parse(&args(&["8", "--seed", "-1"])).is_err());
+    }
+
+    #[test]
+    fn diff_parse_works() {
+        assert_eq!(
+            DiffArgs::parse(&args(&["all", "--seed", "7", "--cases", "20", "--size", "10"])),
+            Ok(DiffArgs { target: Target::All, seed: Some(7), cases: 20, size: Some(10) })
+        );
+        assert_eq!(
+            DiffArgs::parse(&args(&["4"])),
+            Ok(DiffArgs { target: Target::Day(4), seed: None, cases: DEFAULT_CASES, size: None })
+        );
+        assert!(DiffArgs::parse(&args(&[])).is_err());
+        assert!(DiffArgs::parse(&args(&["4", "--cases", "0"])).is_err());
+    }
+
+    #[test]
+    fn batch_parse_works() {
+        let batch_args = BatchArgs::parse(&args(&["7", "--dir", "team", "--csv", "--jobs", "2"])).unwrap();
+        assert_eq!((batch_args.run.target, batch_args.dir.as_str(), batch_args.csv), (Target::Day(7), "team", true));
+        assert_eq!(batch_args.run.jobs, Some(2));
+
+        assert!(BatchArgs::parse(&args(&["7"])).is_err());
+        assert!(BatchArgs::parse(&args(&["all", "--dir", "team"])).is_err());
+        assert!(BatchArgs::parse(&args(&["7", "--dir", "team", "--input", "-"])).is_err());
+        assert!(BatchArgs::parse(&args(&["7", "--dir", "team", "--json", "--csv"])).is_err());
+    }
+
+    #[test]
+    fn lint_parse_works() {
+        assert_eq!(LintArgs::parse(&args(&[])), Ok(LintArgs { target: Target::All, input: None, fix: false }));
+        assert_eq!(
+            LintArgs::parse(&args(&["5", "--input", "day-5.txt", "--fix"])),
+            Ok(LintArgs { target: Target::Day(5), input: Some(String::from("day-5.txt")), fix: true })
+        );
+        assert!(LintArgs::parse(&args(&["all", "--input", "day-5.txt"])).is_err());
+        assert!(LintArgs::parse(&args(&["5", "--input", "-", "--fix"])).is_err());
+    }
+
+    #[test]
+    fn bench_parse_works() {
+        assert_eq!(
+            BenchArgs::parse(&args(&["9", "--runs", "3", "--baseline", "bench.txt"])),
+            Ok(BenchArgs {
+                run: RunArgs { target: Target::Day(9), input: None, json: false, example: false, render: None, jobs: None, timeout: None, config: None, set: vec![], trace: None, trace_steps: None, no_cache: false },
+                runs: 3,
+                save_baseline: None,
+                baseline: Some(String::from("bench.txt")),
+            })
+        );
+        assert_eq!(BenchArgs::parse(&args(&["all"])).unwrap().runs, DEFAULT_RUNS);
+        assert!(BenchArgs::parse(&args(&["all", "--runs", "0"])).is_err());
+        assert!(BenchArgs::parse(&args(&["--save-baseline"])).is_err());
+        assert!(BenchArgs::parse(&args(&["all", "--jobs", "2"])).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs;
+use std::path::PathBuf;
+
+use common::json;
+use common::report::PartResult;
+
+// one input's answers, named after its file
+pub struct Row {
+    pub name: String,
+    pub parts: Vec<PartResult>,
+}
+
+impl Row {
+    pub fn failed(&self) -> bool {
+        return self.parts.iter().any(|part| part.outcome.is_err());
+    }
+}
+
+// the files in `dir` in name order, leaving out hidden ones like .DS_Store
+pub fn input_files(dir: &str) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("could not read inputs from {}: {}", dir, e))?;
+    let mut files = vec![];
+
+    for entry in entries {
+        let path = entry.map_err(|e| format!("could not read inputs from {}: {}", dir, e))?.path();
+        let hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
+        if path.is_file() && !hidden {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    return Ok(files);
+}
+
+pub fn to_json(rows: &[Row]) -> String {
+    let mut results = vec![];
+
+    for row in rows.iter() {
+        for part in row.parts.iter() {
+            let mut fields = vec![("input", json::string(&row.name))];
+            fields.extend(part.json_fields());
+            results.push(format!("    {}", json::object(&fields)));
+        }
+    }
+
+    if results.is_empty() {
+        return String::from("{\n  \"results\": []\n}");
+    }
+
+    return format!("{{\n  \"results\": [\n{}\n  ]\n}}", results.join(",\n"));
+}
+
+// a row per input, with timings in nanoseconds and failures in their own column
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut lines = vec![String::from("input,part_1,part_1_ns,part_2,part_2_ns,error")];
+
+    for row in rows.iter() {
+        let mut cells = vec![csv_field(&row.name)];
+        let mut errors = vec![];
+
+        for part in row.parts.iter() {
+            match &part.outcome {
+                Ok(answer) => cells.push(csv_field(&answer.to_string())),
+                Err(reason) => {
+                    cells.push(String::new());
+                    errors.push(format!("part {}: {}", part.part, reason));
+                }
+            }
+            cells.push(part.elapsed.map(|elapsed| elapsed.as_nanos().to_string()).unwrap_or_default());
+        }
+
+        cells.push(csv_field(&errors.join("; ")));
+        lines.push(cells.join(","));
+    }
+
+    return lines.join("\n") + "\n";
+}
+
+// quoted only when it has to be, day 10's answer being several lines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+
+    return value.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Answer;
+    use std::time::Duration;
+
+    fn rows() -> Vec<Row> {
+        let answered = |part, answer| PartResult { day: 10, part, outcome: Ok(answer), elapsed: Some(Duration::from_nanos(42)) };
+
+        return vec![
+            Row { name: String::from("alice.txt"), parts: vec![answered(1, Answer::from("#.\n.#")), answered(2, Answer::from(3))] },
+            Row {
+                name: String::from("bob, again.txt"),
+                parts: vec![PartResult::failed(10, 1, "parse error"), PartResult::failed(10, 2, "parse error")],
+            },
+        ];
+    }
+
+    #[test]
+    fn to_csv_works() {
+        assert_eq!(
+            to_csv(&rows()),
+            "input,part_1,part_1_ns,part_2,part_2_ns,error\n\
+             alice.txt,\"#.\n.#\",42,3,42,\n\
+             \"bob, again.txt\",,,,,part 1: parse error; part 2: parse error\n"
+        );
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn to_json_works() {
+        let json = to_json(&rows());
+
+        assert!(json.contains("{\"input\": \"alice.txt\", \"day\": 10, \"part\": 2, \"answer\": 3,"));
+        assert!(json.contains("{\"input\": \"bob, again.txt\", \"day\": 10, \"part\": 1, \"answer\": null,"));
+        assert_eq!(to_json(&[]), "{\n  \"results\": []\n}");
+    }
+
+    #[test]
+    fn failed_works() {
+        let rows = rows();
+        assert_eq!((rows[0].failed(), rows[1].failed()), (false, true));
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hint;
+use std::time::{Duration, Instant};
+
+use common::ParseError;
+
+pub const PHASES: [&str; 3] = ["parse", "part-1", "part-2"];
+
+// how much slower than the baseline a median can get before it counts as a regression
+const REGRESSION_THRESHOLD: f64 = 0.2;
+
+// anything under this is scheduler noise rather than a slower solution
+const NOISE_FLOOR: Duration = Duration::from_micros(100);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+
+        return Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        };
+    }
+}
+
+pub fn time<T, F: FnMut() -> T>(runs: usize, mut f: F) -> Stats {
+    let mut samples = vec![];
+
+    for _i in 0..runs {
+        let start = Instant::now();
+        hint::black_box(f());
+        samples.push(start.elapsed());
+    }
+
+    return Stats::from_samples(&mut samples);
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        return format!("{}ns", nanos);
+    } else if nanos < 1_000_000 {
+        return format!("{:.1}µs", nanos as f64 / 1e3);
+    } else if nanos < 1_000_000_000 {
+        return format!("{:.2}ms", nanos as f64 / 1e6);
+    }
+
+    return format!("{:.2}s", nanos as f64 / 1e9);
+}
+
+/*
+ * Median times from an earlier run, one "<day> <phase> <nanoseconds>" per
+ * line so the file diffs nicely when it is checked in.
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, String), Duration>,
+}
+
+impl Baseline {
+    pub fn parse(input: &str) -> Result<Baseline, ParseError> {
+        let mut baseline = Baseline::default();
+
+        for (index, line) in input.lines().enumerate() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.is_empty() {
+                continue;
+            }
+
+            let parsed = match words.as_slice() {
+                [day, phase, nanos] if PHASES.contains(phase) => {
+                    day.parse::<u8>().ok().zip(nanos.parse::<u64>().ok()).map(|entry| (entry, phase))
+                }
+                _ => None,
+            };
+
+            match parsed {
+                Some(((day, nanos), phase)) => baseline.insert(day, phase, Duration::from_nanos(nanos)),
+                None => {
+                    let error = ParseError::new("<day> <parse|part-1|part-2> <nanoseconds>", line, 1);
+                    return Err(error.at_line(index + 1));
+                }
+            }
+        }
+
+        return Ok(baseline);
+    }
+
+    pub fn insert(&mut self, day: u8, phase: &str, median: Duration) {
+        self.medians.insert((day, phase.to_string()), median);
+    }
+
+    pub fn get(&self, day: u8, phase: &str) -> Option<Duration> {
+        return self.medians.get(&(day, phase.to_string())).cloned();
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((day, phase), median) in self.medians.iter() {
+            writeln!(f, "{} {} {}", day, phase, median.as_nanos())?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub change: f64,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    pub fn new(baseline: Duration, median: Duration) -> Comparison {
+        let baseline_nanos = baseline.as_nanos().max(1) as f64;
+        let change = (median.as_nanos() as f64 - baseline_nanos) / baseline_nanos;
+        let regressed = change > REGRESSION_THRESHOLD && median.saturating_sub(baseline) > NOISE_FLOOR;
+
+        return Comparison { change, regressed };
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+.0}%", self.change * 100.0)?;
+
+        if self.regressed {
+            write!(f, " REGRESSED")?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_works() {
+        let mut samples = vec![Duration::from_millis(3), Duration::from_millis(1), Duration::from_millis(2)];
+        assert_eq!(
+            Stats::from_samples(&mut samples),
+            Stats { min: Duration::from_millis(1), median: Duration::from_millis(2), max: Duration::from_millis(3) }
+        );
+    }
+
+    #[test]
+    fn format_duration_works() {
+        assert_eq!(format_duration(Duration::from_nanos(950)), "950ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.56ms");
+        assert_eq!(format_duration(Duration::from_millis(1_500)), "1.50s");
+    }
+
+    #[test]
+    fn baseline_round_trips() {
+        let mut baseline = Baseline::default();
+        baseline.insert(9, "part-2", Duration::from_nanos(412_000_000));
+        baseline.insert(5, "parse", Duration::from_nanos(1_200));
+
+        let written = baseline.to_string();
+        assert_eq!(written, "5 parse 1200\n9 part-2 412000000\n");
+        assert_eq!(Baseline::parse(&written), Ok(baseline));
+
+        assert_eq!(Baseline::parse("5 parse 1200\n9 part-3 10").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn comparison_works() {
+        let comparison = Comparison::new(Duration::from_millis(100), Duration::from_millis(150));
+        assert_eq!(comparison.to_string(), "+50% REGRESSED");
+
+        let comparison = Comparison::new(Duration::from_millis(100), Duration::from_millis(90));
+        assert_eq!(comparison.to_string(), "-10%");
+
+        // twice as slow, but only by a few microseconds
+        assert!(!Comparison::new(Duration::from_micros(5), Duration::from_micros(10)).regressed);
+    }
+}
@@ -0,0 +1,352 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use common::report::PartResult;
+use common::{Answer, Params};
+
+// a cache directory to use instead of the default one
+pub const CACHE_VAR: &str = "AOC_CACHE_DIR";
+pub const DEFAULT_DIR: &str = ".aoc-cache";
+
+const HEADER: &str = "aoc cache";
+
+// numbers the files being written, batch workers can save the same entry at once
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/*
+ * Answers from earlier runs, one file per day and input, so rerunning
+ * everything only solves what changed. An entry is keyed on a hash of the
+ * input, the day's parameters and the runner binary itself, so a rebuild with
+ * different code never reuses an old answer:
+ *
+ *   aoc cache
+ *   day 7
+ *   input 4f1c0e2b9a7d3356
+ *   build 9e07b1d1c2a4f8e0
+ *   part 1 text 41500 CABDFE
+ *   part 2 integer 1200000 1081
+ *
+ * The number before each answer is how long it took in nanoseconds. Newlines
+ * in an answer (day 10's picture) are written as \n.
+ *
+ * The timeout isn't part of the key. Only finished answers are saved, and an
+ * answer doesn't depend on how long it was allowed to take, so one found with a
+ * generous --timeout is still right for a run with a short one.
+ */
+pub struct Cache {
+    dir: PathBuf,
+    build: u64,
+}
+
+// one cached day, as read back for `aoc cache`
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub input: u64,
+    pub build: u64,
+    pub parts: Vec<PartResult>,
+    path: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf, build: u64) -> Cache {
+        return Cache { dir, build };
+    }
+
+    // the directory from AOC_CACHE_DIR or the default, for the binary that's running
+    pub fn open() -> Result<Cache, String> {
+        let dir = env::var(CACHE_VAR).unwrap_or_else(|_| DEFAULT_DIR.to_string());
+        return Ok(Cache::new(PathBuf::from(dir), build_hash()?));
+    }
+
+    pub fn dir(&self) -> &Path {
+        return &self.dir;
+    }
+
+    // whether `entry` was saved by the binary that's running
+    pub fn is_current(&self, entry: &Entry) -> bool {
+        return entry.build == self.build;
+    }
+
+    // the answers saved for this input, if there are any from this build
+    pub fn get(&self, day: u8, input: &str, params: &Params) -> Option<Vec<PartResult>> {
+        let text = fs::read_to_string(self.path(day, input, params)).ok()?;
+        let entry = parse_entry(&text).ok()?;
+
+        // a hash collision, or a file from somewhere else
+        if entry.day != day || entry.input != hash(input.as_bytes()) || entry.build != self.build {
+            return None;
+        }
+
+        return Some(entry.parts);
+    }
+
+    // only a day with both answers is saved, a timeout or a panic is worth trying again
+    pub fn put(&self, day: u8, input: &str, params: &Params, parts: &[PartResult]) -> Result<(), String> {
+        let mut text = format!("{}\nday {}\ninput {:016x}\nbuild {:016x}\n", HEADER, day, hash(input.as_bytes()), self.build);
+
+        for part in parts.iter() {
+            let answer = match &part.outcome {
+                Ok(answer) => answer,
+                Err(_) => return Ok(()),
+            };
+            // an answer that wouldn't read back the same isn't worth keeping
+            if Answer::from_text(answer.type_name(), &answer.to_string()).as_ref() != Some(answer) {
+                return Ok(());
+            }
+
+            let elapsed = part.elapsed.unwrap_or_default().as_nanos();
+            text.push_str(&format!("part {} {} {} {}\n", part.part, answer.type_name(), elapsed, escape(&answer.to_string())));
+        }
+
+        let error = |e: std::io::Error| format!("could not save answers to {}: {}", self.dir.display(), e);
+        fs::create_dir_all(&self.dir).map_err(error)?;
+
+        // written aside and moved into place, so a run that's interrupted can't leave half an entry
+        let path = self.path(day, input, params);
+        let partial = path.with_extension(format!("{}-{}.partial", process::id(), WRITES.fetch_add(1, Ordering::Relaxed)));
+        fs::write(&partial, text).map_err(error)?;
+        return fs::rename(&partial, &path).map_err(error);
+    }
+
+    // everything in the cache by day, anything that isn't an entry is left out
+    pub fn entries(&self) -> Result<Vec<Entry>, String> {
+        let files = match fs::read_dir(&self.dir) {
+            Ok(files) => files,
+            Err(_) if !self.dir.exists() => return Ok(vec![]),
+            Err(e) => return Err(format!("could not read the cache in {}: {}", self.dir.display(), e)),
+        };
+
+        let mut entries = vec![];
+        for file in files {
+            let path = file.map_err(|e| format!("could not read the cache in {}: {}", self.dir.display(), e))?.path();
+
+            if let Ok(Ok(mut entry)) = fs::read_to_string(&path).map(|text| parse_entry(&text)) {
+                entry.path = path;
+                entries.push(entry);
+            }
+        }
+
+        entries.sort_by_key(|entry| (entry.day, entry.input, entry.build));
+        return Ok(entries);
+    }
+
+    // removes every entry, or only the ones an older build saved, and says how many went
+    pub fn clear(&self, old_only: bool) -> Result<usize, String> {
+        let mut removed = 0;
+
+        for entry in self.entries()? {
+            if old_only && self.is_current(&entry) {
+                continue;
+            }
+
+            fs::remove_file(&entry.path).map_err(|e| format!("could not remove {}: {}", entry.path.display(), e))?;
+            removed += 1;
+        }
+
+        return Ok(removed);
+    }
+
+    fn path(&self, day: u8, input: &str, params: &Params) -> PathBuf {
+        let mut key = format!("{}\0{:016x}\0", day, self.build).into_bytes();
+        key.extend_from_slice(input.as_bytes());
+
+        for (name, value) in params.pairs() {
+            key.extend_from_slice(format!("\0{}={}", name, value).as_bytes());
+        }
+
+        return self.dir.join(format!("day-{}-{:016x}", day, hash(&key)));
+    }
+}
+
+// FNV-1a, nothing here needs more than telling inputs apart
+pub fn hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for byte in bytes.iter() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    return hash;
+}
+
+// the runner binary stands in for the solver version, any change to any day makes a new one
+fn build_hash() -> Result<u64, String> {
+    static BUILD: OnceLock<Result<u64, String>> = OnceLock::new();
+
+    return BUILD
+        .get_or_init(|| {
+            let path = env::current_exe().map_err(|e| format!("could not find the aoc binary to cache answers for: {}", e))?;
+            let bytes = fs::read(&path).map_err(|e| format!("could not read {} to cache answers for: {}", path.display(), e))?;
+            Ok(hash(&bytes))
+        })
+        .clone();
+}
+
+fn parse_entry(text: &str) -> Result<Entry, String> {
+    let mut lines = text.lines();
+
+    if lines.next() != Some(HEADER) {
+        return Err(String::from("not a cache entry"));
+    }
+
+    let mut entry = Entry { day: 0, input: 0, build: 0, parts: vec![], path: PathBuf::new() };
+
+    for line in lines.filter(|line| !line.is_empty()) {
+        let invalid = || format!("invalid cache line: {}", line);
+        let (name, value) = line.split_once(' ').ok_or_else(invalid)?;
+
+        match name {
+            "day" => entry.day = value.parse().map_err(|_| invalid())?,
+            "input" => entry.input = u64::from_str_radix(value, 16).map_err(|_| invalid())?,
+            "build" => entry.build = u64::from_str_radix(value, 16).map_err(|_| invalid())?,
+            "part" => entry.parts.push(parse_part(entry.day, value).ok_or_else(invalid)?),
+            _ => return Err(invalid()),
+        }
+    }
+
+    if entry.day == 0 || entry.parts.len() != 2 {
+        return Err(String::from("incomplete cache entry"));
+    }
+
+    return Ok(entry);
+}
+
+// "2 integer 1200000 1081"
+fn parse_part(day: u8, text: &str) -> Option<PartResult> {
+    let mut fields = text.splitn(4, ' ');
+    let part = fields.next()?.parse().ok()?;
+    let type_name = fields.next()?;
+    let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let answer = Answer::from_text(type_name, &unescape(fields.next()?))?;
+
+    return Some(PartResult { day, part, outcome: Ok(answer), elapsed: Some(elapsed) });
+}
+
+fn escape(text: &str) -> String {
+    return text.replace('\\', "\\\\").replace('\n', "\\n");
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    return unescaped;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // a cache of its own for each test, tests run side by side
+    fn scratch(name: &str, build: u64) -> Cache {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}-{}", name, process::id()));
+        fs::remove_dir_all(&dir).ok();
+
+        return Cache::new(dir, build);
+    }
+
+    fn answered(day: u8, part: u8, answer: Answer) -> PartResult {
+        return PartResult { day, part, outcome: Ok(answer), elapsed: Some(Duration::from_nanos(1500)) };
+    }
+
+    #[test]
+    fn get_works() {
+        let cache = scratch("get", 1);
+        let params = Params::from_pairs(&[("workers", "2")]);
+        let parts = vec![
+            answered(10, 1, Answer::Bitmap(vec![String::from("#..#"), String::from("\\..#")])),
+            answered(10, 2, Answer::Integer(10355)),
+        ];
+
+        assert_eq!(cache.get(10, "stars", &params), None);
+        cache.put(10, "stars", &params, &parts).unwrap();
+        assert_eq!(cache.get(10, "stars", &params), Some(parts.clone()));
+
+        // anything that could change the answer is a miss
+        assert_eq!(cache.get(10, "other stars", &params), None);
+        assert_eq!(cache.get(10, "stars", &Params::default()), None);
+        assert_eq!(Cache::new(cache.dir().to_path_buf(), 2).get(10, "stars", &params), None);
+
+        fs::remove_dir_all(cache.dir()).ok();
+    }
+
+    #[test]
+    fn put_from_threads_works() {
+        let cache = scratch("threads", 1);
+        let parts = vec![answered(1, 1, Answer::Integer(3)), answered(1, 2, Answer::Integer(2))];
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| cache.put(1, "+1\n-2\n+3\n+1", &Params::default(), &parts).unwrap());
+            }
+        });
+
+        assert_eq!(cache.get(1, "+1\n-2\n+3\n+1", &Params::default()), Some(parts.clone()));
+        assert_eq!(fs::read_dir(cache.dir()).unwrap().count(), 1);
+
+        fs::remove_dir_all(cache.dir()).ok();
+    }
+
+    #[test]
+    fn put_skips_failures() {
+        let cache = scratch("put", 1);
+        let parts = vec![answered(9, 1, Answer::Integer(408679)), PartResult::failed(9, 2, "TIMEOUT")];
+
+        cache.put(9, "marbles", &Params::default(), &parts).unwrap();
+        assert_eq!(cache.get(9, "marbles", &Params::default()), None);
+        assert_eq!(cache.entries(), Ok(vec![]));
+    }
+
+    #[test]
+    fn clear_works() {
+        let old = scratch("clear", 1);
+        let current = Cache::new(old.dir().to_path_buf(), 2);
+        let parts = |day| vec![answered(day, 1, Answer::Integer(1)), answered(day, 2, Answer::from("CABDFE"))];
+
+        old.put(7, "steps", &Params::default(), &parts(7)).unwrap();
+        current.put(7, "steps", &Params::default(), &parts(7)).unwrap();
+        current.put(3, "claims", &Params::default(), &parts(3)).unwrap();
+        fs::write(old.dir().join("notes.txt"), "not an entry").unwrap();
+
+        let entries = current.entries().unwrap();
+        assert_eq!(entries.iter().map(|entry| (entry.day, current.is_current(entry))).collect::<Vec<_>>(), vec![(3, true), (7, false), (7, true)]);
+        assert_eq!(entries[0].parts, parts(3));
+
+        assert_eq!(current.clear(true), Ok(1));
+        assert_eq!(current.entries().unwrap().len(), 2);
+        assert_eq!(current.clear(false), Ok(2));
+        assert_eq!(current.entries(), Ok(vec![]));
+        assert!(old.dir().join("notes.txt").exists());
+
+        fs::remove_dir_all(old.dir()).ok();
+    }
+
+    #[test]
+    fn escape_works() {
+        for text in ["#..#\n####", "a\\nb", "trailing\\", ""] {
+            assert_eq!(unescape(&escape(text)), text);
+        }
+        assert_eq!(escape("#.\n.#"), "#.\\n.#");
+    }
+}
@@ -0,0 +1,161 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use common::report;
+use common::ParseError;
+
+// past this many the rest are usually the same mistake again
+pub const MAX_PROBLEMS: usize = 50;
+
+const BOM: char = '\u{feff}';
+
+/*
+ * Problems with how the input was saved rather than what it says: the sort of
+ * thing pasting from a browser or editing on Windows leaves behind. Every one
+ * of them is something `normalize` takes care of.
+ */
+pub fn check_format(text: &str) -> Vec<ParseError> {
+    let mut problems = vec![];
+
+    if text.trim().is_empty() {
+        return vec![ParseError::new("some input", "", 1)];
+    }
+
+    if text.starts_with(BOM) {
+        problems.push(ParseError::new("no byte order mark at the start", "", 1));
+    }
+
+    let lines: Vec<&str> = text.trim_start_matches(BOM).split('\n').collect();
+    // a file ending in a newline splits into one last empty piece, which isn't a line
+    let lines = if lines.last() == Some(&"") { &lines[..lines.len() - 1] } else { &lines[..] };
+
+    // blank lines at the end are one problem, not a trailing whitespace problem each
+    let trailing_blank = lines.iter().rev().take_while(|line| line.trim().is_empty()).count();
+    let content = lines.len() - trailing_blank;
+
+    for (index, raw) in lines[..content].iter().enumerate() {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+
+        if line.len() < raw.len() {
+            problems.push(ParseError::new("\\n line endings instead of \\r\\n", line, line.len() + 1).at_line(index + 1));
+        }
+
+        let trimmed = line.trim_end();
+        if trimmed.len() < line.len() {
+            problems.push(ParseError::new("no trailing whitespace", line, trimmed.len() + 1).at_line(index + 1));
+        }
+    }
+
+    if trailing_blank > 0 {
+        problems.push(ParseError::new("no blank lines at the end", "", 1).at_line(content + 1));
+    }
+
+    return problems;
+}
+
+// the input with everything check_format complains about fixed, keeping every line where it was
+pub fn normalize(text: &str) -> String {
+    let lines: Vec<&str> = text.trim_start_matches(BOM).lines().map(|line| line.trim_end()).collect();
+    let content = lines.join("\n");
+    let content = content.trim_end_matches('\n');
+
+    if content.is_empty() {
+        return String::new();
+    }
+
+    return format!("{}\n", content);
+}
+
+/*
+ * Parsing stops at the first mistake, so to find the rest the line it blamed
+ * is taken out and the input parsed again, up to MAX_PROBLEMS times. Line
+ * numbers are mapped back to where each line was in the original input.
+ */
+pub fn check_grammar<F: Fn(&str) -> Result<(), ParseError>>(text: &str, parse: F) -> Vec<ParseError> {
+    let mut lines: Vec<(usize, &str)> = text.lines().enumerate().map(|(index, line)| (index + 1, line)).collect();
+    let mut problems = vec![];
+
+    while problems.len() < MAX_PROBLEMS && !lines.is_empty() {
+        let remaining: Vec<&str> = lines.iter().map(|&(_, line)| line).collect();
+
+        let error = match panic::catch_unwind(AssertUnwindSafe(|| parse(&remaining.join("\n")))) {
+            Ok(Ok(())) => break,
+            Ok(Err(error)) => error,
+            // there's no telling which line did it, so this points at the first one left
+            Err(payload) => {
+                let expected = format!("input the solver can parse without panicking, but it {}", report::panicked(&*payload));
+                let (original, line) = lines[0];
+
+                problems.push(ParseError::new(&expected, line, 1).at_line(original));
+                break;
+            }
+        };
+
+        // an error past the last line (like a tree missing its end) can't be taken out
+        if error.line == 0 || error.line > lines.len() {
+            problems.push(error);
+            break;
+        }
+
+        let (original, _) = lines.remove(error.line - 1);
+        problems.push(error.at_line(original));
+    }
+
+    return problems;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_format_works() {
+        assert!(check_format("+1\n-2\n").is_empty());
+        assert!(check_format("+1\n-2").is_empty());
+
+        let problems = check_format("+1 \r\n-2\r\n\n\n");
+        let found: Vec<(usize, usize, &str)> =
+            problems.iter().map(|problem| (problem.line, problem.column, problem.expected.as_str())).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (1, 4, "\\n line endings instead of \\r\\n"),
+                (1, 3, "no trailing whitespace"),
+                (2, 3, "\\n line endings instead of \\r\\n"),
+                (3, 1, "no blank lines at the end"),
+            ]
+        );
+        assert_eq!(check_format(" \n").len(), 1);
+        assert_eq!(check_format("initial\n \nnotes")[0].line, 2);
+        assert_eq!(check_format("\u{feff}+1\n")[0].expected, "no byte order mark at the start");
+    }
+
+    #[test]
+    fn normalize_works() {
+        assert_eq!(normalize("\u{feff}+1 \r\n-2\r\n\n\n"), "+1\n-2\n");
+        assert_eq!(normalize("+1"), "+1\n");
+        assert_eq!(normalize("initial\n\nnotes"), "initial\n\nnotes\n");
+        assert_eq!(normalize("\n \n"), "");
+        assert!(check_format(&normalize("+1 \r\n-2\r\n\n\n")).is_empty());
+    }
+
+    fn numbers(text: &str) -> Result<(), ParseError> {
+        return common::parse_lines(text, |line| line.parse::<i64>().map_err(|_| ParseError::new("a number", line, 1)))
+            .map(|_| ());
+    }
+
+    #[test]
+    fn check_grammar_finds_every_line() {
+        assert!(check_grammar("1\n2\n3", numbers).is_empty());
+
+        let lines: Vec<usize> = check_grammar("1\nx\n3\ny\nz", numbers).iter().map(|problem| problem.line).collect();
+        assert_eq!(lines, vec![2, 4, 5]);
+
+        let many = vec!["x"; MAX_PROBLEMS * 2].join("\n");
+        assert_eq!(check_grammar(&many, numbers).len(), MAX_PROBLEMS);
+
+        let problems = check_grammar("x\n2", |input| if input.starts_with('x') { numbers(input) } else { panic!("boom") });
+        let found: Vec<(usize, &str, &str)> = problems.iter().map(|problem| (problem.line, problem.text.as_str(), problem.expected.as_str())).collect();
+        assert_eq!(found[1], (2, "2", "input the solver can parse without panicking, but it panicked: boom"));
+    }
+}
@@ -0,0 +1,810 @@
+mod args;
+mod batch;
+mod bench;
+mod cache;
+mod lint;
+mod pool;
+mod table;
+mod verify;
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use args::{BatchArgs, BenchArgs, CacheArgs, DiffArgs, GenerateArgs, LintArgs, RunArgs, StepArgs, Target};
+use bench::{Baseline, Comparison, Stats};
+use cache::Cache;
+use common::cli;
+use common::config::Config;
+use common::expected::Expected;
+use common::input::Source;
+use common::report::{self, PartResult};
+use common::rng::Rng;
+use common::snapshot::Snapshot;
+use common::stepper::{self, Stepper};
+use common::trace::Trace;
+use days::Day;
+use table::Table;
+use verify::Verdict;
+
+const USAGE: &str = "Usage: aoc run <day|all> [--input <path>|-] [--json] [--example] [--render <path>] [--jobs <n>] [--timeout <secs>]
+                [--trace <path> [--trace-steps <from>..<to>]] [--no-cache]
+       aoc verify [day|all] [--input <path>|-] [--jobs <n>] [--timeout <secs>] [--no-cache]
+       aoc bench <day|all> [--input <path>|-] [--runs <n>] [--baseline <path>] [--save-baseline <path>]
+       aoc generate <day> [--seed <n>] [--size <n>]
+       aoc diff <day|all> [--seed <n>] [--cases <n>] [--size <n>]
+       aoc lint [day|all] [--input <path>|-] [--fix]
+       aoc batch <day> --dir <inputs> [--json|--csv] [--jobs <n>] [--timeout <secs>] [--no-cache]
+       aoc step <7|9|10|12> [--input <path>] [--resume <snapshot>]
+       aoc cache [show|clear [--old]]
+
+run, verify, bench, batch and step also take [--config <path>] [--set <[day.]key=value>] to change a day's parameters.
+The config defaults to aoc.toml, or AOC_CONFIG when that's set.
+Answers are cached in .aoc-cache, or AOC_CACHE_DIR when that's set. --no-cache solves again and refreshes them.";
+
+const DEFAULT_CONFIG: &str = "aoc.toml";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => match RunArgs::parse(&args[1..]) {
+            Ok(run_args) => run(&run_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("verify") => {
+            // checking everything is the common case, so the target is optional here
+            let mut verify_args = args[1..].to_vec();
+            if verify_args.first().is_none_or(|arg| arg.starts_with("--")) {
+                verify_args.insert(0, String::from("all"));
+            }
+
+            match RunArgs::parse(&verify_args) {
+                Ok(run_args) if run_args.json || run_args.example || run_args.render.is_some() || run_args.trace.is_some() => {
+                    exit_with_usage("--json, --example, --render and --trace are only supported by run")
+                }
+                Ok(run_args) => verify(&run_args),
+                Err(message) => exit_with_usage(&message),
+            }
+        }
+        Some("bench") => match BenchArgs::parse(&args[1..]) {
+            Ok(bench_args) => bench(&bench_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("generate") => match GenerateArgs::parse(&args[1..]) {
+            Ok(generate_args) => generate(&generate_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("diff") => match DiffArgs::parse(&args[1..]) {
+            Ok(diff_args) => diff(&diff_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("batch") => match BatchArgs::parse(&args[1..]) {
+            Ok(batch_args) => batch(&batch_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("lint") => match LintArgs::parse(&args[1..]) {
+            Ok(lint_args) => lint(&lint_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("step") => match StepArgs::parse(&args[1..]) {
+            Ok(step_args) => step(&step_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        Some("cache") => match CacheArgs::parse(&args[1..]) {
+            Ok(cache_args) => show_cache(&cache_args),
+            Err(message) => exit_with_usage(&message),
+        },
+        _ => exit_with_usage("expected a command"),
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
+
+fn selected_days(target: &Target) -> Vec<Day> {
+    match target {
+        Target::All => days::all(),
+        Target::Day(number) => match days::find(*number) {
+            Some(day) => vec![day],
+            None => exit_with_usage(&format!("Unknown day: {}", number)),
+        },
+    }
+}
+
+fn run(run_args: &RunArgs) {
+    let days = selected_days(&run_args.target);
+
+    if run_args.example {
+        return run_examples(&days);
+    }
+
+    let mut table = Table::new(&["Day", "Part 1", "Part 2"]);
+    let mut results = vec![];
+
+    let config = load_config(run_args);
+    let trace = run_args.trace.as_ref().map(|path| match Trace::to_file(path, run_args.trace_steps.clone().unwrap_or(0..u64::MAX)) {
+        Ok(trace) => trace,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    });
+
+    let cache = open_cache();
+    let solved = in_parallel(&days, run_args, |day, source| solve(day, source, run_args, &config, cache.as_ref(), trace.as_ref()));
+    let cached = solved.iter().filter(|solved| solved.cached).count();
+
+    if let Some(Err(message)) = trace.as_ref().map(Trace::flush) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+
+    for (day, solved) in days.iter().zip(solved) {
+        solved.print_messages();
+
+        let parts = solved.parts;
+        table.add_row(vec![day.number.to_string(), parts[0].text(), parts[1].text()]);
+        results.extend(parts);
+    }
+
+    if run_args.json {
+        println!("{}", report::to_json(&results));
+    } else {
+        print!("{}", table);
+        print_cached(cached, "day(s)");
+    }
+
+    // --render only takes a single day
+    if let (Some(path), [day]) = (&run_args.render, days.as_slice()) {
+        let source = Source::for_day(day.number, run_args.input.as_deref(), &day.input_path());
+
+        if let Err(message) = render(day, &source, &config, path) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+// how wide the views are drawn when the shell doesn't say
+const DEFAULT_COLUMNS: usize = 80;
+
+fn step(step_args: &StepArgs) {
+    let run_args = &step_args.run;
+    let day = &selected_days(&run_args.target)[0];
+    let source = Source::for_day(day.number, run_args.input.as_deref(), &day.input_path());
+    let config = load_config(run_args);
+
+    let solution = match load(day, &source, &config) {
+        Ok(solution) => solution,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    };
+
+    if solution.simulate().is_none() {
+        eprintln!("day {} isn't a simulation, try day 7, 9, 10 or 12", day.number);
+        process::exit(1);
+    }
+
+    let snapshot = step_args.resume.as_ref().map(|path| match check_snapshot(day, solution.as_ref(), path) {
+        Ok(snapshot) => snapshot,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    });
+
+    // going back past a snapshot starts again from the snapshot, not the beginning
+    let width = env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()).unwrap_or(DEFAULT_COLUMNS);
+    let mut stepper = Stepper::new(|| match &snapshot {
+        Some(snapshot) => solution.resume(snapshot).unwrap(),
+        None => solution.simulate().unwrap(),
+    });
+
+    if let Err(e) = stepper::run(&mut stepper, &mut io::stdin().lock(), &mut io::stdout(), width) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+// a snapshot this day can carry on from
+fn check_snapshot(day: &Day, solution: &dyn common::Solution, path: &str) -> Result<Snapshot, String> {
+    let snapshot = Snapshot::load(path)?;
+
+    if snapshot.day != day.number {
+        return Err(format!("{} is a snapshot of day {}, not day {}", path, snapshot.day, day.number));
+    }
+
+    solution.resume(&snapshot).map_err(|e| format!("{}: {}", path, e))?;
+    return Ok(snapshot);
+}
+
+// the day's solution for an input, parsed and configured
+fn load(day: &Day, source: &Source, config: &Config) -> Result<Box<dyn common::Solution>, String> {
+    let input = source.read().map_err(|e| e.to_string())?;
+    let mut solution = (day.parse)(&input).map_err(|e| e.in_file(&source.to_string()).to_string())?;
+    common::configure(solution.as_mut(), &config.params(day.number))?;
+
+    return Ok(solution);
+}
+
+fn render(day: &Day, source: &Source, config: &Config, path: &str) -> Result<(), String> {
+    let solution = load(day, source, config)?;
+
+    return cli::save_render(day.number, solution.as_ref(), path);
+}
+
+fn run_examples(days: &[Day]) {
+    let mut table = Table::new(&["Day", "Example", "Part 1", "Part 2"]);
+    let mut failed = false;
+
+    for day in days.iter() {
+        for example in day.examples.iter() {
+            let mut row = vec![day.number.to_string(), example.name.to_string()];
+
+            match example.check(day.parse) {
+                Ok(checked) => {
+                    for part in 1..=2 {
+                        match checked.iter().find(|check| check.part == part) {
+                            Some(check) => {
+                                failed |= !check.passed();
+                                row.push(check.cell());
+                            }
+                            None => row.push(String::from("-")),
+                        }
+                    }
+                }
+                Err(message) => {
+                    eprintln!("day {}: {}", day.number, message);
+                    failed = true;
+                    row.push(String::from("error"));
+                    row.push(String::from("error"));
+                }
+            }
+
+            table.add_row(row);
+        }
+    }
+
+    print!("{}", table);
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn verify(run_args: &RunArgs) {
+    let days = selected_days(&run_args.target);
+
+    let mut table = Table::new(&["Day", "Part 1", "Part 2"]);
+    let mut diffs = vec![];
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    let config = load_config(run_args);
+    let cache = open_cache();
+    let checked = in_parallel(&days, run_args, |day, source| {
+        let mut solved = solve(day, source, run_args, &config, cache.as_ref(), None);
+        let expected = match Expected::load(source) {
+            Ok(expected) => expected,
+            Err(message) => {
+                solved.messages.insert(0, format!("day {}: {}", day.number, message));
+                Expected::default()
+            }
+        };
+
+        return (expected, solved);
+    });
+
+    let cached = checked.iter().filter(|(_, solved)| solved.cached).count();
+
+    for (day, (expected, solved)) in days.iter().zip(checked) {
+        solved.print_messages();
+
+        let mut row = vec![day.number.to_string()];
+
+        for result in solved.parts.iter() {
+            let part = result.part;
+            let actual = result.text();
+            let verdict = verify::check(expected.part(part), &actual);
+
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail => {
+                    failed += 1;
+                    let diff = verify::diff(expected.part(part).unwrap(), &actual);
+                    diffs.push(format!("day {} part {}:\n{}", day.number, part, diff));
+                }
+                Verdict::Missing => missing += 1,
+            }
+
+            row.push(verdict.to_string());
+        }
+
+        table.add_row(row);
+    }
+
+    print!("{}", table);
+
+    for diff in diffs.iter() {
+        println!("\n{}", diff);
+    }
+
+    println!("\n{} passed, {} failed, {} missing", passed, failed, missing);
+    print_cached(cached, "day(s)");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn bench(bench_args: &BenchArgs) {
+    let days = selected_days(&bench_args.run.target);
+
+    let baseline = match &bench_args.baseline {
+        Some(path) => match load_baseline(path) {
+            Ok(baseline) => Some(baseline),
+            Err(message) => exit_with_usage(&message),
+        },
+        None => None,
+    };
+
+    let config = load_config(&bench_args.run);
+    let mut table = Table::new(&["Day", "Phase", "Min", "Median", "Max", "Baseline"]);
+    let mut results = Baseline::default();
+    let mut regressions = 0;
+
+    for day in days.iter() {
+        let source = Source::for_day(day.number, bench_args.run.input.as_deref(), &day.input_path());
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("day {}: {}", day.number, e);
+                continue;
+            }
+        };
+
+        let mut solution = match panic::catch_unwind(|| (day.parse)(&input)) {
+            Ok(Ok(solution)) => solution,
+            Ok(Err(e)) => {
+                eprintln!("{}", e.in_file(&source.to_string()));
+                continue;
+            }
+            Err(payload) => {
+                eprintln!("day {}: {} while parsing", day.number, report::panicked(&*payload));
+                continue;
+            }
+        };
+
+        if let Err(message) = common::configure(solution.as_mut(), &config.params(day.number)) {
+            eprintln!("day {}: {}", day.number, message);
+            continue;
+        }
+
+        for phase in bench::PHASES.iter() {
+            let runs = bench_args.runs;
+            let timed = panic::catch_unwind(AssertUnwindSafe(|| match *phase {
+                "parse" => bench::time(runs, || (day.parse)(&input)),
+                "part-1" => bench::time(runs, || solution.part_1()),
+                _ => bench::time(runs, || solution.part_2()),
+            }));
+
+            let stats: Stats = match timed {
+                Ok(stats) => stats,
+                Err(payload) => {
+                    table.add_row(vec![day.number.to_string(), phase.to_string(), report::panicked(&*payload)]);
+                    continue;
+                }
+            };
+
+            let comparison = baseline
+                .as_ref()
+                .and_then(|baseline| baseline.get(day.number, phase))
+                .map(|previous| Comparison::new(previous, stats.median));
+
+            if comparison.as_ref().is_some_and(|comparison| comparison.regressed) {
+                regressions += 1;
+            }
+
+            results.insert(day.number, phase, stats.median);
+            table.add_row(vec![
+                day.number.to_string(),
+                phase.to_string(),
+                bench::format_duration(stats.min),
+                bench::format_duration(stats.median),
+                bench::format_duration(stats.max),
+                comparison.map(|comparison| comparison.to_string()).unwrap_or_default(),
+            ]);
+        }
+    }
+
+    print!("{}", table);
+
+    if let Some(path) = &bench_args.save_baseline {
+        if let Err(e) = fs::write(path, results.to_string()) {
+            eprintln!("could not save baseline to {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+
+    if regressions > 0 {
+        println!("\n{} phase(s) slower than the baseline", regressions);
+        process::exit(1);
+    }
+}
+
+fn generate(generate_args: &GenerateArgs) {
+    let day = match days::find(generate_args.day) {
+        Some(day) => day,
+        None => exit_with_usage(&format!("Unknown day: {}", generate_args.day)),
+    };
+
+    let seed = seed_or_random(generate_args.seed);
+    let size = generate_args.size.unwrap_or(day.default_size);
+    let input = (day.generate)(&mut Rng::new(seed), size);
+
+    // inputs are often piped into head, a closed pipe isn't worth a panic
+    let _ = writeln!(io::stdout(), "{}", input);
+}
+
+/*
+ * Feeds generated inputs to each day's reference and real solvers and stops
+ * a day at the first input they disagree on. Seeds count up from the first
+ * one, so any case can be made again with aoc generate.
+ */
+fn diff(diff_args: &DiffArgs) {
+    let days = selected_days(&diff_args.target);
+    let first_seed = seed_or_random(diff_args.seed);
+
+    let mut table = Table::new(&["Day", "Cases", "Result"]);
+    let mut mismatches = vec![];
+
+    for day in days.iter() {
+        let max_size = diff_args.size.unwrap_or(day.reference.max_size);
+        let mismatch = day.reference.find_mismatch(day.parse, day.generate, first_seed, diff_args.cases, max_size);
+
+        match mismatch {
+            Some(mismatch) => {
+                table.add_row(vec![day.number.to_string(), diff_args.cases.to_string(), format!("part {} differs", mismatch.part)]);
+                mismatches.push((day, mismatch));
+            }
+            None => table.add_row(vec![day.number.to_string(), diff_args.cases.to_string(), String::from("agree")]),
+        }
+    }
+
+    print!("{}", table);
+
+    for (day, mismatch) in mismatches.iter() {
+        let params: Vec<String> = day.reference.params.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+
+        println!("\nday {} part {}, seed {}, size {}", day.number, mismatch.part, mismatch.seed, mismatch.size);
+        if !params.is_empty() {
+            println!("params: {}", params.join(", "));
+        }
+        println!("reference:\n{}", mismatch.reference);
+        println!("solver:\n{}", mismatch.solver);
+        println!("reproduce with: aoc generate {} --seed {} --size {}", day.number, mismatch.seed, mismatch.size);
+        println!("input:\n{}", mismatch.input);
+    }
+
+    if !mismatches.is_empty() {
+        process::exit(1);
+    }
+}
+
+// solves every input in a directory for one day, like everyone on the team's puzzle
+fn batch(batch_args: &BatchArgs) {
+    let run_args = &batch_args.run;
+    let day = selected_days(&run_args.target).remove(0);
+
+    let files = match batch::input_files(&batch_args.dir) {
+        Ok(files) => files,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    };
+
+    let config = load_config(run_args);
+    let cache = open_cache();
+    let workers = run_args.jobs.unwrap_or_else(pool::default_workers);
+    let solved = pool::map_in_order(files.iter().collect(), workers, |path| {
+        solve(&day, &Source::File(path.clone()), run_args, &config, cache.as_ref(), None)
+    });
+    let cached = solved.iter().filter(|solved| solved.cached).count();
+
+    let mut rows = vec![];
+    for (path, solved) in files.iter().zip(solved) {
+        solved.print_messages();
+
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        rows.push(batch::Row { name, parts: solved.parts });
+    }
+
+    let failed = rows.iter().filter(|row| row.failed()).count();
+
+    if run_args.json {
+        println!("{}", batch::to_json(&rows));
+    } else if batch_args.csv {
+        print!("{}", batch::to_csv(&rows));
+    } else {
+        let mut table = Table::new(&["Input", "Part 1", "Time", "Part 2", "Time"]);
+        let timing = |part: &PartResult| part.elapsed.map(bench::format_duration).unwrap_or_else(|| String::from("-"));
+
+        for row in rows.iter() {
+            let (part_1, part_2) = (&row.parts[0], &row.parts[1]);
+            table.add_row(vec![row.name.clone(), part_1.text(), timing(part_1), part_2.text(), timing(part_2)]);
+        }
+
+        print!("{}", table);
+        println!("\n{} input(s), {} failed", rows.len(), failed);
+        print_cached(cached, "input(s)");
+    }
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/*
+ * Checks how each input was saved and then whether its day can parse it,
+ * without solving anything. Formatting problems are checked on the input as
+ * it is, the grammar on the normalized input so a stray \r isn't blamed on
+ * every line.
+ */
+fn lint(lint_args: &LintArgs) {
+    let days = selected_days(&lint_args.target);
+
+    let mut table = Table::new(&["Day", "Format", "Grammar"]);
+    let mut reports = vec![];
+    let (mut unfixed, mut fixable) = (0, 0);
+
+    for day in days.iter() {
+        let source = Source::for_day(day.number, lint_args.input.as_deref(), &day.input_path());
+        let text = match source.read() {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("day {}: {}", day.number, e);
+                unfixed += 1;
+                table.add_row(vec![day.number.to_string(), String::from("no input"), String::from("-")]);
+                continue;
+            }
+        };
+
+        let format = lint::check_format(&text);
+        let normalized = lint::normalize(&text);
+        let grammar = if normalized.is_empty() {
+            vec![]
+        } else {
+            lint::check_grammar(&normalized, |text| (day.parse)(text).map(|_| ()))
+        };
+
+        let mut fixed = false;
+        if lint_args.fix && !format.is_empty() && !normalized.is_empty() {
+            match fs::write(source.to_string(), &normalized) {
+                Ok(()) => fixed = true,
+                Err(e) => eprintln!("day {}: could not fix {}: {}", day.number, source, e),
+            }
+        }
+
+        if !fixed {
+            unfixed += format.len();
+            // normalizing can't make up an input that isn't there
+            if !normalized.is_empty() {
+                fixable += format.len();
+            }
+        }
+        unfixed += grammar.len();
+
+        let format_cell = match (format.len(), fixed) {
+            (0, _) => String::from("ok"),
+            (count, true) => format!("{} fixed", count),
+            (count, false) => format!("{} problem(s)", count),
+        };
+        let grammar_cell = match grammar.len() {
+            _ if normalized.is_empty() => String::from("-"),
+            0 => String::from("ok"),
+            count => format!("{} problem(s)", count),
+        };
+
+        table.add_row(vec![day.number.to_string(), format_cell, grammar_cell]);
+
+        for problem in format.into_iter().chain(grammar) {
+            reports.push(problem.in_file(&source.to_string()));
+        }
+    }
+
+    print!("{}", table);
+
+    for report in reports.iter() {
+        println!("\n{}", report);
+    }
+
+    if fixable > 0 {
+        println!("\nrun with --fix to normalize line endings, trailing whitespace and blank lines");
+    }
+
+    if unfixed > 0 {
+        process::exit(1);
+    }
+}
+
+// what's in the answer cache, or empties it
+fn show_cache(cache_args: &CacheArgs) {
+    let cache = match Cache::open() {
+        Ok(cache) => cache,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    };
+
+    let outcome = match cache_args {
+        CacheArgs::Show => cache.entries().map(|entries| {
+            let mut table = Table::new(&["Day", "Input", "Part 1", "Part 2", "Build"]);
+            let old = entries.iter().filter(|entry| !cache.is_current(entry)).count();
+
+            for entry in entries.iter() {
+                let build = if cache.is_current(entry) { "current" } else { "old" };
+                let input = format!("{:016x}", entry.input)[..8].to_string();
+                table.add_row(vec![entry.day.to_string(), input, entry.parts[0].text(), entry.parts[1].text(), build.to_string()]);
+            }
+
+            print!("{}", table);
+            println!("\n{} answer(s) in {}, {} from an older build", entries.len(), cache.dir().display(), old);
+            if old > 0 {
+                println!("run aoc cache clear --old to remove the ones this build can't use");
+            }
+        }),
+        CacheArgs::Clear { old } => cache.clear(*old).map(|removed| {
+            println!("removed {} answer(s) from {}", removed, cache.dir().display());
+        }),
+    };
+
+    if let Err(message) = outcome {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+// without a seed pick one, but say which so a bad input can be made again
+fn seed_or_random(seed: Option<u64>) -> u64 {
+    if let Some(seed) = seed {
+        return seed;
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seed = now.as_nanos() as u64;
+    eprintln!("seed: {}", seed);
+
+    return seed;
+}
+
+fn load_baseline(path: &str) -> Result<Baseline, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => return Err(format!("could not read baseline from {}: {}", path, e)),
+    };
+
+    return Baseline::parse(&contents).map_err(|e| e.in_file(path).to_string());
+}
+
+// what solving a day produced, with its errors kept back so days running side by side don't interleave them
+struct Solved {
+    parts: Vec<PartResult>,
+    messages: Vec<String>,
+    // the answers came from the cache instead of being solved this time
+    cached: bool,
+}
+
+impl Solved {
+    fn print_messages(&self) {
+        for message in self.messages.iter() {
+            eprintln!("{}", message);
+        }
+    }
+}
+
+// runs `work` for every day on the thread pool, handing back the results in day order
+fn in_parallel<R, F>(days: &[Day], run_args: &RunArgs, work: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(&Day, &Source) -> R + Sync,
+{
+    let workers = run_args.jobs.unwrap_or_else(pool::default_workers);
+
+    return pool::map_in_order(days.iter().collect(), workers, |day| {
+        let source = Source::for_day(day.number, run_args.input.as_deref(), &day.input_path());
+        return work(day, &source);
+    });
+}
+
+// the config file with the command line's overrides on top, exiting if either is wrong
+fn load_config(run_args: &RunArgs) -> Config {
+    let single_day = match run_args.target {
+        Target::Day(number) => Some(number),
+        Target::All => None,
+    };
+
+    let loaded = Config::load(run_args.config.as_deref(), DEFAULT_CONFIG).and_then(|mut config| {
+        for setting in run_args.set.iter() {
+            config.apply_override(single_day, setting)?;
+        }
+        Ok(config)
+    });
+
+    match loaded {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+// answers are only a shortcut, so a cache that can't be opened just means solving everything
+fn open_cache() -> Option<Cache> {
+    match Cache::open() {
+        Ok(cache) => Some(cache),
+        Err(message) => {
+            eprintln!("{}", message);
+            None
+        }
+    }
+}
+
+// `what` is whatever was solved, days or inputs
+fn print_cached(cached: usize, what: &str) {
+    if cached > 0 {
+        println!("\n{} {} answered from the cache, run with --no-cache to solve again", cached, what);
+    }
+}
+
+fn solve(day: &Day, source: &Source, run_args: &RunArgs, config: &Config, cache: Option<&Cache>, trace: Option<&Trace>) -> Solved {
+    let failed = |reason: &str, message: String| Solved {
+        parts: vec![PartResult::failed(day.number, 1, reason), PartResult::failed(day.number, 2, reason)],
+        messages: vec![message],
+        cached: false,
+    };
+
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(e) => return failed("no input", format!("day {}: {}", day.number, e)),
+    };
+
+    // a trace needs the steps to actually happen
+    let params = config.params(day.number);
+    if let (Some(cache), false, None) = (cache, run_args.no_cache, trace) {
+        if let Some(parts) = cache.get(day.number, &input, &params) {
+            return Solved { parts, messages: vec![], cached: true };
+        }
+    }
+
+    // a day that blows up shouldn't stop the rest of the table from printing
+    let mut solution = match panic::catch_unwind(|| (day.parse)(&input)) {
+        Ok(Ok(solution)) => solution,
+        Ok(Err(e)) => return failed("parse error", e.in_file(&source.to_string()).to_string()),
+        Err(payload) => return failed("panicked", format!("day {}: {} while parsing", day.number, report::panicked(&*payload))),
+    };
+
+    if let Err(message) = common::configure(solution.as_mut(), &params) {
+        return failed("config error", format!("day {}: {}", day.number, message));
+    }
+
+    let budget = run_args.timeout.unwrap_or(args::DEFAULT_TIMEOUT);
+    let parts = report::solve_parts(day.number, solution.as_ref(), Some(budget), trace);
+
+    let mut messages = vec![];
+    if let Some(Err(message)) = cache.map(|cache| cache.put(day.number, &input, &params, &parts)) {
+        messages.push(format!("day {}: {}", day.number, message));
+    }
+
+    return Solved { parts, messages, cached: false };
+}
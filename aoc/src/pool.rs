@@ -0,0 +1,84 @@
+use std::num::NonZeroUsize;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+// one worker per core unless told otherwise
+pub fn default_workers() -> usize {
+    return thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1);
+}
+
+/*
+ * Runs `work` over every item on up to `workers` threads and hands the results
+ * back in the same order as the items, however the work happened to finish.
+ * Workers take the next item as soon as they're free, so one slow item doesn't
+ * hold up a whole batch.
+ */
+pub fn map_in_order<T, R, F>(items: Vec<T>, workers: usize, work: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let total = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, total.max(1)) {
+            let sender = sender.clone();
+            let (queue, work) = (&queue, &work);
+
+            scope.spawn(move || loop {
+                // the lock is only held long enough to take the next item
+                let next = queue.lock().unwrap().next();
+
+                match next {
+                    Some((index, item)) => sender.send((index, work(item))).unwrap(),
+                    None => break,
+                }
+            });
+        }
+    });
+
+    drop(sender);
+
+    let mut results: Vec<(usize, R)> = receiver.into_iter().collect();
+    results.sort_by_key(|&(index, _)| index);
+
+    return results.into_iter().map(|(_, result)| result).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn results_stay_in_order() {
+        // the early items take longest, so they finish last
+        let results = map_in_order((0..8).collect(), 4, |item: u64| {
+            thread::sleep(Duration::from_millis(8 - item));
+            item * 10
+        });
+
+        assert_eq!(results, vec![0, 10, 20, 30, 40, 50, 60, 70]);
+        assert_eq!(map_in_order(Vec::<u64>::new(), 4, |item| item), vec![]);
+    }
+
+    #[test]
+    fn workers_are_capped() {
+        let (running, most) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        map_in_order((0..12).collect(), 3, |_item: u64| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        assert!(most.load(Ordering::SeqCst) <= 3);
+        assert_eq!(map_in_order((0..3).collect(), 0, |item: u64| item), vec![0, 1, 2]);
+    }
+}
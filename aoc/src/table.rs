@@ -0,0 +1,93 @@
+use std::fmt;
+
+/*
+ * A plain text table where each cell can span multiple lines (day 10's answer
+ * is a picture), so the widest line in a column decides that column's width.
+ */
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Table {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|header| header.len()).collect();
+
+        for row in self.rows.iter() {
+            for (column, cell) in row.iter().enumerate() {
+                for line in cell.lines() {
+                    if line.len() > widths[column] {
+                        widths[column] = line.len();
+                    }
+                }
+            }
+        }
+
+        return widths;
+    }
+}
+
+fn write_row(f: &mut fmt::Formatter, cells: &[String], widths: &[usize]) -> fmt::Result {
+    let lines: Vec<Vec<&str>> = cells.iter().map(|cell| cell.lines().collect()).collect();
+    let height = lines.iter().map(|cell| cell.len()).max().unwrap_or(0).max(1);
+
+    for index in 0..height {
+        let mut line = String::new();
+
+        for (column, cell) in lines.iter().enumerate() {
+            if column > 0 {
+                line.push_str("  ");
+            }
+
+            line.push_str(&format!("{:width$}", cell.get(index).unwrap_or(&""), width = widths[column]));
+        }
+
+        writeln!(f, "{}", line.trim_end())?;
+    }
+
+    return Ok(());
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths = self.column_widths();
+        let divider: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+        write_row(f, &self.headers, &widths)?;
+        write_row(f, &divider, &widths)?;
+
+        for row in self.rows.iter() {
+            write_row(f, row, &widths)?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        let mut table = Table::new(&["Day", "Part 1"]);
+        table.add_row(vec![String::from("7"), String::from("CABDFE")]);
+        table.add_row(vec![String::from("10"), String::from("#..#\n####")]);
+
+        assert_eq!(
+            table.to_string(),
+            "Day  Part 1\n---  ------\n7    CABDFE\n10   #..#\n     ####\n"
+        );
+    }
+}
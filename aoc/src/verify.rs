@@ -0,0 +1,71 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail => write!(f, "FAIL"),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+pub fn check(expected: Option<&str>, actual: &str) -> Verdict {
+    match expected {
+        Some(expected) if expected == actual => Verdict::Pass,
+        Some(_) => Verdict::Fail,
+        None => Verdict::Missing,
+    }
+}
+
+/*
+ * Line by line, so a single wrong pixel in day 10's picture stands out:
+ * matching lines are indented, expected lines get a - and actual lines a +.
+ */
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut lines = vec![];
+
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(a), Some(b)) if a == b => lines.push(format!("  {}", a)),
+            (a, b) => {
+                if let Some(a) = a {
+                    lines.push(format!("- {}", a));
+                }
+                if let Some(b) = b {
+                    lines.push(format!("+ {}", b));
+                }
+            }
+        }
+    }
+
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_works() {
+        assert_eq!(check(Some("1081"), "1081"), Verdict::Pass);
+        assert_eq!(check(Some("1081"), "1080"), Verdict::Fail);
+        assert_eq!(check(None, "1081"), Verdict::Missing);
+    }
+
+    #[test]
+    fn diff_works() {
+        assert_eq!(diff("1081", "1080"), "- 1081\n+ 1080");
+        assert_eq!(diff("#..#\n.##.", "#..#\n.#.."), "  #..#\n- .##.\n+ .#..");
+        assert_eq!(diff("#..#", "#..#\n...."), "  #..#\n+ ....");
+    }
+}
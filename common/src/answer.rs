@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::json;
+
+/*
+ * Every part of every day produces one of these so answers can be compared
+ * and printed the same way no matter which day they came from.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    // e.g. the top left corner of a square, optionally followed by its size
+    Coordinate(Vec<i64>),
+    // rows of a picture that has to be read by a human (day 10's message)
+    Bitmap(Vec<String>),
+    // the input has no answer for this part, and this says why
+    Unsolved(String),
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Coordinate(_) => "coordinate",
+            Answer::Bitmap(_) => "bitmap",
+            Answer::Unsolved(_) => "unsolved",
+        }
+    }
+
+    // the answer back from its type_name and how it's printed, None when the two don't fit
+    pub fn from_text(type_name: &str, text: &str) -> Option<Answer> {
+        let answer = match type_name {
+            "integer" => Answer::Integer(text.parse().ok()?),
+            "text" => Answer::Text(text.to_string()),
+            "coordinate" if text.is_empty() => Answer::Coordinate(vec![]),
+            "coordinate" => Answer::Coordinate(text.split(',').map(|value| value.parse().ok()).collect::<Option<_>>()?),
+            "bitmap" => Answer::Bitmap(text.split('\n').map(|row| row.to_string()).collect()),
+            _ => return None,
+        };
+
+        return Some(answer);
+    }
+
+    // numbers stay numbers, and a bitmap becomes an array of its rows
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Integer(value) => value.to_string(),
+            Answer::Text(value) => json::string(value),
+            Answer::Coordinate(values) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                format!("[{}]", values.join(", "))
+            }
+            Answer::Bitmap(rows) => {
+                let rows: Vec<String> = rows.iter().map(|row| json::string(row)).collect();
+                format!("[{}]", rows.join(", "))
+            }
+            Answer::Unsolved(_) => String::from("null"),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Coordinate(values) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "{}", values.join(","))
+            }
+            Answer::Bitmap(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::Unsolved(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        return Answer::Integer(value);
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        return Answer::Integer(i64::from(value));
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Answer {
+        return Answer::Integer(i64::from(value));
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        return Answer::Integer(value as i64);
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        return Answer::Text(value);
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        return Answer::Text(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        assert_eq!(Answer::Integer(-42).to_string(), "-42");
+        assert_eq!(Answer::Text(String::from("CABDFE")).to_string(), "CABDFE");
+        assert_eq!(Answer::Coordinate(vec![90, 269, 16]).to_string(), "90,269,16");
+        assert_eq!(Answer::Bitmap(vec![String::from("#..#"), String::from("####")]).to_string(), "#..#\n####");
+    }
+
+    #[test]
+    fn to_json_works() {
+        assert_eq!(Answer::Integer(-42).to_json(), "-42");
+        assert_eq!(Answer::Text(String::from("CABDFE")).to_json(), "\"CABDFE\"");
+        assert_eq!(Answer::Coordinate(vec![235, 60]).to_json(), "[235, 60]");
+        assert_eq!(Answer::Bitmap(vec![String::from("#..#"), String::from("####")]).to_json(), "[\"#..#\", \"####\"]");
+        assert_eq!(Answer::Unsolved(String::from("no message")).to_json(), "null");
+    }
+
+    #[test]
+    fn from_text_works() {
+        let answers = vec![
+            Answer::Integer(-42),
+            Answer::Text(String::from("CABDFE")),
+            Answer::Coordinate(vec![90, 269, 16]),
+            Answer::Bitmap(vec![String::from("#..#"), String::from("####")]),
+        ];
+
+        for answer in answers {
+            assert_eq!(Answer::from_text(answer.type_name(), &answer.to_string()), Some(answer));
+        }
+
+        assert_eq!(Answer::from_text("integer", "CABDFE"), None);
+        assert_eq!(Answer::from_text("coordinate", "90,x"), None);
+        assert_eq!(Answer::from_text("picture", "#..#"), None);
+        assert_eq!(Answer::from_text("unsolved", "no message"), None);
+    }
+
+    #[test]
+    fn from_works() {
+        assert_eq!(Answer::from(138_usize), Answer::Integer(138));
+        assert_eq!(Answer::from("fgij"), Answer::Text(String::from("fgij")));
+    }
+}
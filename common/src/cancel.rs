@@ -0,0 +1,138 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/*
+ * Cooperative cancellation for solvers that can run for a very long time. The
+ * runner hands a Token to `within`, and long loops ask `requested()` every so
+ * often and give up early when it says so. A Token is cancelled once its time
+ * budget runs out or when `cancel` is called from anywhere holding a clone.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+// the work didn't finish inside its token's budget, or was cancelled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cancelled;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+impl Token {
+    // only cancelled by calling `cancel`
+    pub fn new() -> Token {
+        return Token::default();
+    }
+
+    pub fn with_budget(budget: Duration) -> Token {
+        return Token { cancelled: Arc::default(), deadline: Some(Instant::now() + budget) };
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.cancel();
+            return true;
+        }
+
+        return false;
+    }
+}
+
+/*
+ * Runs `f` with `token` as this thread's current token. Loops that never check
+ * can't be stopped, but their result is still thrown away if they finish after
+ * the token was cancelled, so running over the budget always reads as Cancelled.
+ */
+pub fn within<T, F: FnOnce() -> T>(token: &Token, f: F) -> Result<T, Cancelled> {
+    let previous = CURRENT.with(|current| current.replace(Some(token.clone())));
+
+    // put the previous token back even if `f` panics
+    struct Restore(Option<Token>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(previous);
+    let value = f();
+
+    if token.is_cancelled() {
+        return Err(Cancelled);
+    }
+
+    return Ok(value);
+}
+
+// whether the work running on this thread should stop, always false outside `within`
+pub fn requested() -> bool {
+    return CURRENT.with(|current| current.borrow().as_ref().is_some_and(Token::is_cancelled));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn budgets_run_out() {
+        let token = Token::with_budget(Duration::from_millis(20));
+
+        let counted = within(&token, || {
+            let mut count = 0;
+            while !requested() {
+                count += 1;
+            }
+            count
+        });
+
+        assert_eq!(counted, Err(Cancelled));
+        assert!(!requested());
+        assert_eq!(within(&Token::with_budget(Duration::from_secs(60)), || 7), Ok(7));
+    }
+
+    #[test]
+    fn cancel_works_across_threads() {
+        let token = Token::new();
+        let remote = token.clone();
+
+        let stopper = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            remote.cancel();
+        });
+
+        let result = within(&token, || while !requested() {});
+        stopper.join().unwrap();
+
+        assert_eq!(result, Err(Cancelled));
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn tokens_nest() {
+        let outer = Token::new();
+        let inner = Token::new();
+        inner.cancel();
+
+        let result = within(&outer, || {
+            assert!(within(&inner, requested).is_err());
+            requested()
+        });
+
+        assert_eq!(result, Ok(false));
+    }
+}
@@ -0,0 +1,237 @@
+use std::env;
+use std::process;
+
+use crate::config::Config;
+use crate::input::Source;
+use crate::report::{self, PartResult};
+use crate::{Example, Solution};
+
+// every day binary is run from its own crate directory
+const DEFAULT_INPUT: &str = "src/input.txt";
+const DEFAULT_CONFIG: &str = "../aoc.toml";
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: u8,
+    pub input: Option<String>,
+    pub json: bool,
+    pub example: bool,
+    // where to save a picture of the puzzle, the extension picks the format
+    pub render: Option<String>,
+    pub config: Option<String>,
+    // key=value parameter overrides, applied over the config file
+    pub set: Vec<String>,
+}
+
+impl Options {
+    pub fn parse(day: u8, args: &[String]) -> Result<Options, String> {
+        let mut options = Options { day, input: None, json: false, example: false, render: None, config: None, set: vec![] };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => match args.next() {
+                    Some(path) => options.input = Some(path.clone()),
+                    None => return Err(String::from("--input needs a path, or - for stdin")),
+                },
+                "--json" => options.json = true,
+                "--example" => options.example = true,
+                "--render" => match args.next() {
+                    Some(path) => options.render = Some(path.clone()),
+                    None => return Err(String::from("--render needs a path ending in .ppm, .pgm or .svg")),
+                },
+                "--config" => match args.next() {
+                    Some(path) => options.config = Some(path.clone()),
+                    None => return Err(String::from("--config needs a path")),
+                },
+                "--set" => match args.next() {
+                    Some(setting) => options.set.push(setting.clone()),
+                    None => return Err(String::from("--set needs a key=value")),
+                },
+                _ => return Err(format!("unknown argument: {}", arg)),
+            }
+        }
+
+        // examples bring their own input and parameters, and are checked rather than reported
+        let customised = options.input.is_some() || options.json || options.render.is_some();
+        if options.example && (customised || options.config.is_some() || !options.set.is_empty()) {
+            return Err(String::from("--example can't be combined with --input, --json, --render, --config or --set"));
+        }
+
+        return Ok(options);
+    }
+
+    // parses the day binary's own arguments, exiting with its usage on a mistake
+    pub fn from_env(day: u8) -> Options {
+        let args: Vec<String> = env::args().skip(1).collect();
+
+        match Options::parse(day, &args) {
+            Ok(options) => options,
+            Err(message) => {
+                eprintln!("{}", message);
+                eprintln!(
+                    "Usage: day-{} [--input <path>|-] [--json] [--example] [--render <path>] [--config <path>] [--set <key=value>]",
+                    day
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    pub fn source(&self) -> Source {
+        return Source::for_day(self.day, self.input.as_deref(), DEFAULT_INPUT);
+    }
+
+    // the config file with this run's --set overrides on top
+    pub fn config(&self) -> Result<Config, String> {
+        let mut config = Config::load(self.config.as_deref(), DEFAULT_CONFIG)?;
+
+        for setting in self.set.iter() {
+            config.apply_override(Some(self.day), setting)?;
+        }
+
+        return Ok(config);
+    }
+
+    // reads, parses and configures the day's input (and renders it if asked), exiting with the reason if anything fails
+    pub fn load<S: Solution>(&self) -> S {
+        let source = self.source();
+
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        };
+
+        let mut solution = match S::parse(&input) {
+            Ok(solution) => solution,
+            Err(e) => {
+                eprintln!("{}", e.in_file(&source.to_string()));
+                process::exit(1);
+            }
+        };
+
+        let configured = self.config().and_then(|config| crate::configure(&mut solution, &config.params(self.day)));
+        if let Err(message) = configured {
+            eprintln!("day {}: {}", self.day, message);
+            process::exit(1);
+        }
+
+        if let Some(path) = &self.render {
+            if let Err(message) = save_render(self.day, &solution, path) {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+        }
+
+        return solution;
+    }
+
+    // runs the day's examples instead of its input, exiting with 1 if any are wrong
+    pub fn run_examples<S: Solution + 'static>(&self, examples: &[Example]) {
+        let mut failed = false;
+
+        for example in examples.iter() {
+            match example.check(crate::boxed::<S>) {
+                Ok(checked) => {
+                    for check in checked.iter() {
+                        let status = if check.passed() { "ok" } else { "FAIL" };
+                        println!("{} part {}: {} {}", example.name, check.part, status, check.cell());
+                        failed |= !check.passed();
+                    }
+                }
+                Err(message) => {
+                    println!("{}: {}", example.name, message);
+                    failed = true;
+                }
+            }
+        }
+
+        if failed {
+            process::exit(1);
+        }
+    }
+
+    /*
+     * Answers both parts after the day's own wording for each. A part without
+     * an answer (no answer for this input, or a panic) is reported on stderr
+     * instead, and the binary exits with 1 once both have had their turn.
+     */
+    pub fn print_answers<S: Solution>(&self, solution: &S, wording: [&str; 2]) {
+        let parts = report::solve_parts(self.day, solution, None, None);
+
+        for (part, wording) in parts.iter().zip(wording.iter()) {
+            match &part.outcome {
+                Ok(answer) => println!("{}{}", wording, answer),
+                Err(reason) => eprintln!("day {} part {}: {}", self.day, part.part, reason),
+            }
+        }
+
+        exit_if_failed(&parts);
+    }
+
+    // answers both parts as a JSON document instead of the day's own wording, exiting with 1 if either has no answer
+    pub fn print_json<S: Solution>(&self, solution: &S) {
+        let parts = report::solve_parts(self.day, solution, None, None);
+        println!("{}", report::to_json(&parts));
+
+        exit_if_failed(&parts);
+    }
+}
+
+fn exit_if_failed(parts: &[PartResult]) {
+    if parts.iter().any(|part| part.outcome.is_err()) {
+        process::exit(1);
+    }
+}
+
+pub fn save_render<S: Solution + ?Sized>(day: u8, solution: &S, path: &str) -> Result<(), String> {
+    match solution.render() {
+        Some(image) => image.save(path),
+        None => Err(format!("day {} has nothing to render", day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        return values.iter().map(|value| value.to_string()).collect();
+    }
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(
+            Options::parse(7, &args(&[])),
+            Ok(Options { day: 7, input: None, json: false, example: false, render: None, config: None, set: vec![] })
+        );
+        assert_eq!(
+            Options::parse(7, &args(&["--input", "-"])),
+            Ok(Options { day: 7, input: Some(String::from("-")), json: false, example: false, render: None, config: None, set: vec![] })
+        );
+        assert_eq!(
+            Options::parse(7, &args(&["--json"])),
+            Ok(Options { day: 7, input: None, json: true, example: false, render: None, config: None, set: vec![] })
+        );
+        assert_eq!(Options::parse(3, &args(&["--render", "fabric.ppm"])).unwrap().render, Some(String::from("fabric.ppm")));
+        assert!(Options::parse(3, &args(&["--render"])).is_err());
+        assert!(Options::parse(3, &args(&["--render", "fabric.ppm", "--example"])).is_err());
+        assert!(Options::parse(7, &args(&["--example"])).unwrap().example);
+        assert!(Options::parse(7, &args(&["--example", "--json"])).is_err());
+        assert!(Options::parse(7, &args(&["--input"])).is_err());
+        assert!(Options::parse(7, &args(&["--nope"])).is_err());
+    }
+
+    #[test]
+    fn config_works() {
+        let options = Options::parse(7, &args(&["--config", "/does/not/exist.toml"])).unwrap();
+        assert!(options.config().unwrap_err().starts_with("could not read config from /does/not/exist.toml"));
+
+        let options = Options::parse(7, &args(&["--set", "workers=2", "--set", "9.players=10"])).unwrap();
+        assert_eq!(options.set, vec![String::from("workers=2"), String::from("9.players=10")]);
+        assert!(Options::parse(7, &args(&["--example", "--set", "workers=2"])).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+
+use crate::parser::{chars_while, literal, optional, parse_line, unsigned, whitespace, Input, Parsed, Parser};
+use crate::{parse_lines, Params, ParseError};
+
+// a config file to use instead of the default one
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+/*
+ * Each day's parameters, read from a small TOML-like file so a different
+ * puzzle's numbers don't need a recompile:
+ *
+ *   # comments and blank lines are ignored
+ *   [day-7]
+ *   workers = 5
+ *   step_offset = 60
+ *
+ *   [day-12]
+ *   initial_state = "#..#.#..##......###...###"
+ *
+ * Values are plain numbers or words, or quoted when they hold spaces or #.
+ */
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Config {
+    days: BTreeMap<u8, BTreeMap<String, String>>,
+}
+
+enum Line {
+    Blank,
+    Section(u8),
+    Setting(String, String),
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Config, ParseError> {
+        let mut config = Config::default();
+        let mut day = None;
+
+        for (index, line) in parse_lines(text, |line| parse_line(line, config_line))?.into_iter().enumerate() {
+            match line {
+                Line::Blank => {}
+                Line::Section(number) => day = Some(number),
+                Line::Setting(key, value) => match day {
+                    Some(day) => config.set(day, &key, &value),
+                    None => {
+                        let error = ParseError::new("a [day-N] section before the first setting", text.lines().nth(index).unwrap(), 1);
+                        return Err(error.at_line(index + 1));
+                    }
+                },
+            }
+        }
+
+        return Ok(config);
+    }
+
+    /*
+     * An explicit path has to exist, then AOC_CONFIG is tried, and the default
+     * path is only read when there's something there.
+     */
+    pub fn load(explicit: Option<&str>, default_path: &str) -> Result<Config, String> {
+        let (path, required) = match (explicit, env::var(CONFIG_VAR)) {
+            (Some(path), _) => (path.to_string(), true),
+            (None, Ok(path)) => (path, true),
+            (None, Err(_)) => (default_path.to_string(), false),
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !required => return Ok(Config::default()),
+            Err(e) => return Err(format!("could not read config from {}: {}", path, e)),
+        };
+
+        return Config::parse(&text).map_err(|e| e.in_file(&path).to_string());
+    }
+
+    pub fn set(&mut self, day: u8, key: &str, value: &str) {
+        self.days.entry(day).or_default().insert(key.to_string(), value.to_string());
+    }
+
+    /*
+     * Applies a command line override, either "key=value" for the one day
+     * being run or "day.key=value" when running several.
+     */
+    pub fn apply_override(&mut self, day: Option<u8>, text: &str) -> Result<(), String> {
+        let (name, value) = match text.split_once('=') {
+            Some((name, value)) if !name.is_empty() => (name, value),
+            _ => return Err(format!("expected key=value or day.key=value, got: {}", text)),
+        };
+
+        let (day, key) = match name.split_once('.') {
+            Some((number, key)) => match number.parse() {
+                Ok(number) => (number, key),
+                Err(_) => return Err(format!("expected a day number before the . in: {}", text)),
+            },
+            None => match day {
+                Some(day) => (day, name),
+                None => return Err(format!("which day is {} for? use day.key=value", name)),
+            },
+        };
+
+        self.set(day, key, value);
+        return Ok(());
+    }
+
+    pub fn params(&self, day: u8) -> Params {
+        let mut params = Params::default();
+
+        for (key, value) in self.days.get(&day).into_iter().flatten() {
+            params.set(key, value);
+        }
+
+        return params;
+    }
+}
+
+fn config_line(input: &mut Input) -> Parsed<Line> {
+    whitespace().parse(input)?;
+
+    let section = |input: &mut Input| {
+        literal("[day-").parse(input)?;
+        let day = unsigned().parse(input)?;
+        literal("]").parse(input)?;
+        Ok(Line::Section(day))
+    };
+
+    let line = match optional(section).parse(input)? {
+        Some(section) => section,
+        None => match optional(setting).parse(input)? {
+            Some(setting) => setting,
+            None => Line::Blank,
+        },
+    };
+
+    whitespace().parse(input)?;
+    optional(|input: &mut Input| {
+        literal("#").parse(input)?;
+        chars_while("anything", |_| true).parse(input)
+    })
+    .parse(input)?;
+
+    return Ok(line);
+}
+
+fn setting(input: &mut Input) -> Parsed<Line> {
+    let start = input.offset();
+    let key = chars_while("a key", |c| c.is_ascii_alphanumeric() || c == '_').parse(input)?;
+    if key.is_empty() {
+        return input.fail(start, "a [day-N] section or a key like workers");
+    }
+
+    whitespace().parse(input)?;
+    literal("=").parse(input)?;
+    whitespace().parse(input)?;
+
+    let quoted = |input: &mut Input| {
+        literal("\"").parse(input)?;
+        let value = chars_while("anything but a quote", |c| c != '"').parse(input)?;
+        literal("\"").parse(input)?;
+        Ok(value)
+    };
+
+    let value = match optional(quoted).parse(input)? {
+        Some(value) => value,
+        None => {
+            let start = input.offset();
+            let value = chars_while("a value", |c| !c.is_whitespace() && c != '#' && c != '"').parse(input)?;
+            if value.is_empty() {
+                return input.fail(start, "a value");
+            }
+            value
+        }
+    };
+
+    return Ok(Line::Setting(key, value));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let config = Config::parse(
+            "# the real puzzle\n\n[day-7]\nworkers = 5  # five elves\nstep_offset=60\n\n[day-12]\ninitial_state = \"#..#.#\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.params(7).get("workers", 0), Ok(5));
+        assert_eq!(config.params(7).get("step_offset", 0), Ok(60));
+        assert_eq!(config.params(12).get("initial_state", String::new()), Ok(String::from("#..#.#")));
+        assert!(config.params(9).is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Config::parse("workers = 5").unwrap_err();
+        assert_eq!((error.line, error.column, error.expected.as_str()), (1, 1, "a [day-N] section before the first setting"));
+
+        let error = Config::parse("[day-7]\nworkers 5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+
+        let error = Config::parse("[day-7]\nworkers = \"5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 13));
+
+        let error = Config::parse("[day-seven]").unwrap_err();
+        assert_eq!((error.line, error.column, error.expected.as_str()), (1, 6, "a number"));
+    }
+
+    #[test]
+    fn overrides_work() {
+        let mut config = Config::parse("[day-7]\nworkers = 5").unwrap();
+
+        config.apply_override(Some(7), "workers=2").unwrap();
+        config.apply_override(None, "9.players=10").unwrap();
+
+        assert_eq!(config.params(7).get("workers", 0), Ok(2));
+        assert_eq!(config.params(9).get("players", 0), Ok(10));
+        assert!(config.apply_override(None, "workers=2").is_err());
+        assert!(config.apply_override(Some(7), "workers").is_err());
+        assert!(config.apply_override(Some(7), "seven.workers=2").is_err());
+    }
+}
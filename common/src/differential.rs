@@ -0,0 +1,180 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::report;
+use crate::rng::Rng;
+use crate::{Answer, Example, Params, ParseError, Solution};
+
+/*
+ * A deliberately simple solver for a day: parse with split and unwrap, brute
+ * force everything. It's only ever run on small generated inputs, so being
+ * slow is fine as long as it's obviously right.
+ *
+ * Every day has one in its `reference` module, next to a `generate` module
+ * that makes random inputs shaped like the real one. `aoc generate` prints
+ * those, and `aoc diff` runs both solvers over them.
+ */
+pub struct Reference {
+    pub part_1: fn(&str, &Params) -> Answer,
+    pub part_2: fn(&str, &Params) -> Answer,
+    // applied to both solvers, to shrink problems that are too big to brute force
+    pub params: &'static [(&'static str, &'static str)],
+    // the largest generated input the reference gets through quickly
+    pub max_size: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub size: usize,
+    pub part: u8,
+    pub input: String,
+    pub reference: String,
+    pub solver: String,
+}
+
+impl Reference {
+    // both parts of one input, as (reference, solver) text for the first part that disagrees
+    pub fn compare(
+        &self,
+        parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+        input: &str,
+    ) -> Option<(u8, String, String)> {
+        let params = Params::from_pairs(self.params);
+
+        // generated inputs are always valid, so the solver failing to take one is a mismatch too
+        let mut solver = match parse(input) {
+            Ok(solver) => solver,
+            Err(e) => return Some((1, String::from("no parse error"), e.to_string())),
+        };
+        if let Err(message) = solver.configure(&params) {
+            return Some((1, String::from("no configure error"), message));
+        }
+
+        for part in 1..=2 {
+            let reference = run(|| match part {
+                1 => (self.part_1)(input, &params),
+                _ => (self.part_2)(input, &params),
+            });
+            let solved = run(|| match part {
+                1 => solver.part_1(),
+                _ => solver.part_2(),
+            });
+
+            if reference != solved {
+                return Some((part, reference, solved));
+            }
+        }
+
+        return None;
+    }
+
+    /*
+     * Tries one generated input per seed. Sizes ramp up from 1 to max_size
+     * over the cases, so the first disagreement found tends to be a small one.
+     */
+    pub fn find_mismatch(
+        &self,
+        parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+        generate: fn(&mut Rng, usize) -> String,
+        first_seed: u64,
+        cases: u64,
+        max_size: usize,
+    ) -> Option<Mismatch> {
+        for case in 0..cases {
+            let seed = first_seed + case;
+            let size = 1 + ((max_size.max(1) - 1) as u64 * case / cases.max(2).saturating_sub(1)) as usize;
+            let input = generate(&mut Rng::new(seed), size);
+
+            if let Some((part, reference, solver)) = self.compare(parse, &input) {
+                return Some(Mismatch { seed, size, part, input, reference, solver });
+            }
+        }
+
+        return None;
+    }
+}
+
+fn run<F: FnOnce() -> Answer>(f: F) -> String {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(answer) => answer.to_string(),
+        Err(payload) => report::panicked(&*payload),
+    }
+}
+
+// the reference has to get the puzzle's own examples right before it can judge anything
+pub fn assert_reference_examples(reference: &Reference, examples: &[Example]) {
+    for example in examples.iter() {
+        let params = Params::from_pairs(example.params);
+
+        if let Some(expected) = example.part_1 {
+            assert_eq!((reference.part_1)(example.input, &params).to_string(), expected, "{} part 1", example.name);
+        }
+        if let Some(expected) = example.part_2 {
+            assert_eq!((reference.part_2)(example.input, &params).to_string(), expected, "{} part 2", example.name);
+        }
+    }
+}
+
+// for each day's tests, a handful of small generated inputs
+pub fn assert_agrees<S: Solution + 'static>(reference: &Reference, generate: fn(&mut Rng, usize) -> String) {
+    if let Some(mismatch) = reference.find_mismatch(crate::boxed::<S>, generate, 0, 20, reference.max_size) {
+        panic!(
+            "seed {} size {} part {}: reference {:?}, solver {:?}\n{}",
+            mismatch.seed, mismatch.size, mismatch.part, mismatch.reference, mismatch.solver, mismatch.input
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum {
+        numbers: Vec<i64>,
+    }
+
+    impl Solution for Sum {
+        fn parse(input: &str) -> Result<Sum, ParseError> {
+            Ok(Sum { numbers: input.split(' ').map(|number| number.parse().unwrap()).collect() })
+        }
+
+        fn part_1(&self) -> Answer {
+            return Answer::from(self.numbers.iter().sum::<i64>());
+        }
+
+        // wrong as soon as there is more than one number
+        fn part_2(&self) -> Answer {
+            return Answer::from(self.numbers[0]);
+        }
+    }
+
+    fn sum(input: &str, _params: &Params) -> Answer {
+        return Answer::from(input.split(' ').map(|number| number.parse::<i64>().unwrap()).sum::<i64>());
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let numbers: Vec<String> = (0..size).map(|_| rng.range(1, 9).to_string()).collect();
+        return numbers.join(" ");
+    }
+
+    const REFERENCE: Reference = Reference { part_1: sum, part_2: sum, params: &[], max_size: 10 };
+
+    #[test]
+    fn find_mismatch_works() {
+        let mismatch = REFERENCE.find_mismatch(crate::boxed::<Sum>, generate, 0, 10, 10).unwrap();
+
+        assert_eq!(mismatch.part, 2);
+        assert_eq!(mismatch.size, 2);
+        assert_eq!(mismatch.input.split(' ').count(), 2);
+        assert_ne!(mismatch.reference, mismatch.solver);
+    }
+
+    #[test]
+    fn compare_works() {
+        assert_eq!(REFERENCE.compare(crate::boxed::<Sum>, "4"), None);
+        assert_eq!(
+            REFERENCE.compare(crate::boxed::<Sum>, "4 5"),
+            Some((2, String::from("9"), String::from("4")))
+        );
+    }
+}
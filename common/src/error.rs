@@ -0,0 +1,98 @@
+use std::error;
+use std::fmt;
+
+/*
+ * Where a puzzle input stopped making sense. Line parsers only know the text
+ * they were given and where in it things went wrong, so the line number and
+ * file are filled in by whoever is walking the whole input.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    // both 1 based, like an editor would show them
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(expected: &str, text: &str, column: usize) -> ParseError {
+        ParseError {
+            file: None,
+            line: 1,
+            column,
+            text: text.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        return self;
+    }
+
+    pub fn in_file(mut self, file: &str) -> ParseError {
+        self.file = Some(file.to_string());
+        return self;
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let caret = " ".repeat(self.column.saturating_sub(1));
+
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}", self.line, self.column)?,
+        }
+
+        writeln!(f, ": expected {}", self.expected)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line_number, self.text)?;
+        write!(f, "{} | {}^", gutter, caret)
+    }
+}
+
+impl error::Error for ParseError {}
+
+// runs a parser over every line, tagging any error with the line it came from
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    return input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(line).map_err(|e| e.at_line(index + 1)))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        let error = ParseError::new("a frequency change like +5 or -3", "+x", 2).at_line(12);
+        assert_eq!(
+            error.to_string(),
+            "line 12, column 2: expected a frequency change like +5 or -3\n   |\n12 | +x\n   |  ^"
+        );
+
+        let error = error.in_file("day-1/src/input.txt");
+        assert!(error.to_string().starts_with("day-1/src/input.txt:12:2: expected"));
+    }
+
+    #[test]
+    fn parse_lines_works() {
+        let parse_number = |line: &str| {
+            line.parse::<i32>().map_err(|_| ParseError::new("a number", line, 1))
+        };
+
+        assert_eq!(parse_lines("1\n2\n3", parse_number), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_lines("1\ntwo\n3", parse_number).unwrap_err().line, 2);
+    }
+}
@@ -0,0 +1,151 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::report;
+use crate::{Params, ParseError, Solution};
+
+/*
+ * One of the worked examples from a puzzle's description. Some examples only
+ * illustrate one part (day 2 uses different ids for each), so a part without
+ * an expected answer isn't run at all.
+ */
+#[derive(Debug)]
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    // parameters the description changes for the example, like day 7's worker count
+    pub params: &'static [(&'static str, &'static str)],
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Checked {
+    pub part: u8,
+    pub expected: &'static str,
+    pub actual: String,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        return self.expected == self.actual;
+    }
+
+    // the answer, and what it should have been when the two differ
+    pub fn cell(&self) -> String {
+        if self.passed() {
+            return self.actual.clone();
+        }
+
+        return format!("{}\n(expected {})", self.actual, self.expected);
+    }
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&'static str> {
+        match part {
+            1 => self.part_1,
+            _ => self.part_2,
+        }
+    }
+
+    pub fn check(
+        &self,
+        parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+    ) -> Result<Vec<Checked>, String> {
+        let mut solution = parse(self.input).map_err(|e| e.in_file(self.name).to_string())?;
+        solution.configure(&Params::from_pairs(self.params))?;
+
+        let mut checked = vec![];
+
+        for part in 1..=2 {
+            if let Some(expected) = self.expected(part) {
+                let answer = panic::catch_unwind(AssertUnwindSafe(|| match part {
+                    1 => solution.part_1(),
+                    _ => solution.part_2(),
+                }));
+                let actual = answer.map(|answer| answer.to_string()).unwrap_or_else(|payload| report::panicked(&*payload));
+
+                checked.push(Checked { part, expected, actual });
+            }
+        }
+
+        return Ok(checked);
+    }
+}
+
+// for each day's tests, panicking with every example that went wrong
+pub fn assert_examples<S: Solution + 'static>(examples: &[Example]) {
+    let mut failures = vec![];
+
+    for example in examples.iter() {
+        match example.check(crate::boxed::<S>) {
+            Ok(checked) => {
+                for check in checked.iter().filter(|check| !check.passed()) {
+                    failures.push(format!(
+                        "{} part {}: expected {:?}, got {:?}",
+                        example.name, check.part, check.expected, check.actual
+                    ));
+                }
+            }
+            Err(message) => failures.push(format!("{}: {}", example.name, message)),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    struct Sum {
+        numbers: Vec<i64>,
+        scale: i64,
+    }
+
+    impl Solution for Sum {
+        fn parse(input: &str) -> Result<Sum, ParseError> {
+            let numbers = crate::parse_lines(input, |line| {
+                line.parse().map_err(|_| ParseError::new("a number", line, 1))
+            })?;
+
+            Ok(Sum { numbers, scale: 1 })
+        }
+
+        fn configure(&mut self, params: &Params) -> Result<(), String> {
+            self.scale = params.get("scale", self.scale)?;
+            return Ok(());
+        }
+
+        fn part_1(&self) -> Answer {
+            return Answer::from(self.numbers.iter().sum::<i64>() * self.scale);
+        }
+
+        fn part_2(&self) -> Answer {
+            panic!("part 2 has no example");
+        }
+    }
+
+    #[test]
+    fn check_works() {
+        let example = Example { name: "sum", input: "1\n2", params: &[("scale", "10")], part_1: Some("30"), part_2: None };
+        assert_eq!(
+            example.check(crate::boxed::<Sum>),
+            Ok(vec![Checked { part: 1, expected: "30", actual: String::from("30") }])
+        );
+
+        let example = Example { name: "wrong", input: "1\n2", params: &[], part_1: Some("4"), part_2: None };
+        let checked = example.check(crate::boxed::<Sum>).unwrap();
+        assert_eq!(checked[0].cell(), "3\n(expected 4)");
+
+        let example = Example { name: "bad", input: "1\nx", params: &[], part_1: Some("1"), part_2: None };
+        assert!(example.check(crate::boxed::<Sum>).unwrap_err().starts_with("bad:2:1: expected a number"));
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong part 1: expected \"4\", got \"3\"")]
+    fn assert_examples_reports_failures() {
+        assert_examples::<Sum>(&[Example { name: "wrong", input: "1\n2", params: &[], part_1: Some("4"), part_2: None }]);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::input::Source;
+use crate::ParseError;
+
+/*
+ * The known good answers for one input, written the same way the runner
+ * prints them. An answer may span several lines (day 10 is a picture), so
+ * everything up to the next "part N:" header belongs to the answer before it:
+ *
+ *   part 1:
+ *   #....#..#####.
+ *   #....#..#....#
+ *   part 2: 10355
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct Expected {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Expected {
+    pub fn parse(input: &str) -> Result<Expected, ParseError> {
+        let mut expected = Expected::default();
+        let mut current: Option<(u8, Vec<&str>)> = None;
+
+        for (index, line) in input.lines().enumerate() {
+            if let Some(part) = parse_header(line) {
+                let rest = line[line.find(':').unwrap() + 1..].trim();
+
+                if let Some((part, lines)) = current.take() {
+                    expected.set(part, lines);
+                }
+
+                current = match part {
+                    Some(part) => Some((part, if rest.is_empty() { vec![] } else { vec![rest] })),
+                    None => return Err(ParseError::new("part 1: or part 2:", line, 6).at_line(index + 1)),
+                };
+            } else if let Some((_part, lines)) = current.as_mut() {
+                lines.push(line.trim_end());
+            } else if !line.trim().is_empty() {
+                return Err(ParseError::new("a part 1: or part 2: header", line, 1).at_line(index + 1));
+            }
+        }
+
+        if let Some((part, lines)) = current.take() {
+            expected.set(part, lines);
+        }
+
+        return Ok(expected);
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+
+    // a missing answers file just means nothing has been recorded yet
+    pub fn load(source: &Source) -> Result<Expected, String> {
+        let path = match answers_path(source) {
+            Some(path) => path,
+            None => return Ok(Expected::default()),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Expected::parse(&contents).map_err(|e| e.in_file(&path.display().to_string()).to_string()),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Expected::default()),
+            Err(e) => Err(format!("could not read answers from {}: {}", path.display(), e)),
+        }
+    }
+
+    fn set(&mut self, part: u8, mut lines: Vec<&str>) {
+        while lines.last() == Some(&"") {
+            lines.pop();
+        }
+
+        let answer = Some(lines.join("\n"));
+        match part {
+            1 => self.part_1 = answer,
+            _ => self.part_2 = answer,
+        }
+    }
+}
+
+// Some(None) is a header for a part that doesn't exist
+fn parse_header(line: &str) -> Option<Option<u8>> {
+    let header = line.split(':').next().unwrap();
+    if !line.contains(':') || !header.starts_with("part ") {
+        return None;
+    }
+
+    match header["part ".len()..].trim() {
+        "1" => Some(Some(1)),
+        "2" => Some(Some(2)),
+        _ => Some(None),
+    }
+}
+
+/*
+ * Answers sit next to the input they belong to: day-7/src/input.txt is checked
+ * against day-7/src/answers.txt and $AOC_INPUTS/day-7.txt against
+ * $AOC_INPUTS/day-7.answers.txt. Stdin has nowhere to keep them.
+ */
+pub fn answers_path(source: &Source) -> Option<PathBuf> {
+    match source {
+        Source::File(path) => {
+            let stem = path.file_stem()?.to_string_lossy();
+            let name = if stem == "input" { String::from("answers.txt") } else { format!("{}.answers.txt", stem) };
+
+            Some(path.with_file_name(name))
+        }
+        Source::Stdin => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let expected = Expected::parse("part 1:\n#..#\n.##.\n\npart 2: 10355\n").unwrap();
+        assert_eq!(expected.part(1), Some("#..#\n.##."));
+        assert_eq!(expected.part(2), Some("10355"));
+
+        let expected = Expected::parse("part 2: 1081").unwrap();
+        assert_eq!(expected.part(1), None);
+
+        assert_eq!(Expected::parse("1081").unwrap_err().line, 1);
+        assert_eq!(Expected::parse("part 1: 5\npart 3: 6").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn answers_path_works() {
+        assert_eq!(
+            answers_path(&Source::File(PathBuf::from("day-7/src/input.txt"))),
+            Some(PathBuf::from("day-7/src/answers.txt"))
+        );
+        assert_eq!(
+            answers_path(&Source::File(PathBuf::from("inputs/day-7.txt"))),
+            Some(PathBuf::from("inputs/day-7.answers.txt"))
+        );
+        assert_eq!(answers_path(&Source::Stdin), None);
+    }
+}
@@ -0,0 +1,113 @@
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+// a directory holding one file per day, named day-1.txt through day-12.txt
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /*
+     * An explicit path always wins ("-" meaning stdin), then the inputs
+     * directory from the environment, then the day's checked in input.
+     */
+    pub fn resolve(day: u8, explicit: Option<&str>, inputs_dir: Option<String>, default: &str) -> Source {
+        if let Some(path) = explicit {
+            if path == "-" {
+                return Source::Stdin;
+            }
+
+            return Source::File(PathBuf::from(path));
+        }
+
+        if let Some(dir) = inputs_dir {
+            return Source::File(PathBuf::from(dir).join(format!("day-{}.txt", day)));
+        }
+
+        return Source::File(PathBuf::from(default));
+    }
+
+    pub fn for_day(day: u8, explicit: Option<&str>, default: &str) -> Source {
+        return Source::resolve(day, explicit, env::var(INPUTS_DIR_VAR).ok(), default);
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        let result = match self {
+            Source::File(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+        };
+
+        return result.map_err(|error| InputError { source: self.clone(), error });
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub source: Source,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not read input from {}: {}", self.source, self.error)?;
+
+        if self.error.kind() == io::ErrorKind::NotFound {
+            write!(f, " (pass --input <path>, use - for stdin, or set {})", INPUTS_DIR_VAR)?;
+        }
+
+        return Ok(());
+    }
+}
+
+impl error::Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_works() {
+        assert_eq!(
+            Source::resolve(7, None, None, "src/input.txt"),
+            Source::File(PathBuf::from("src/input.txt"))
+        );
+        assert_eq!(
+            Source::resolve(7, None, Some(String::from("inputs")), "src/input.txt"),
+            Source::File(PathBuf::from("inputs/day-7.txt"))
+        );
+        assert_eq!(
+            Source::resolve(7, Some("mine.txt"), Some(String::from("inputs")), "src/input.txt"),
+            Source::File(PathBuf::from("mine.txt"))
+        );
+        assert_eq!(Source::resolve(7, Some("-"), None, "src/input.txt"), Source::Stdin);
+    }
+
+    #[test]
+    fn missing_file_error_works() {
+        let source = Source::File(PathBuf::from("does/not/exist.txt"));
+        let message = source.read().unwrap_err().to_string();
+
+        assert!(message.starts_with("could not read input from does/not/exist.txt: "));
+        assert!(message.ends_with("(pass --input <path>, use - for stdin, or set AOC_INPUTS)"));
+    }
+}
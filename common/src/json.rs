@@ -0,0 +1,47 @@
+/*
+ * Just enough JSON writing for reporting answers, without pulling in a
+ * serialization crate for a handful of numbers and strings.
+ */
+pub fn string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    return escaped;
+}
+
+// fields are written in the order given, values must already be JSON
+pub fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields.iter().map(|(key, value)| format!("{}: {}", string(key), value)).collect();
+
+    return format!("{{{}}}", fields.join(", "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_works() {
+        assert_eq!(string("AEMN"), "\"AEMN\"");
+        assert_eq!(string("say \"hi\"\\\n"), "\"say \\\"hi\\\"\\\\\\n\"");
+        assert_eq!(string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn object_works() {
+        assert_eq!(object(&[("day", String::from("7")), ("answer", string("CABDFE"))]), "{\"day\": 7, \"answer\": \"CABDFE\"}");
+        assert_eq!(object(&[]), "{}");
+    }
+}
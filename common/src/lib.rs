@@ -0,0 +1,24 @@
+mod answer;
+pub mod cancel;
+pub mod cli;
+pub mod config;
+pub mod differential;
+mod error;
+pub mod example;
+pub mod expected;
+pub mod input;
+pub mod json;
+mod params;
+pub mod parser;
+pub mod report;
+pub mod rng;
+pub mod snapshot;
+mod solution;
+pub mod stepper;
+pub mod trace;
+
+pub use answer::Answer;
+pub use error::{parse_lines, ParseError};
+pub use example::Example;
+pub use params::Params;
+pub use solution::{boxed, configure, Solution};
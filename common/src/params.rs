@@ -0,0 +1,82 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+/*
+ * Named knobs a day exposes, like how many workers day 7 has. The puzzle
+ * text fixes them for the real input, but the examples use smaller values.
+ */
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+    // the keys a day has asked for, anything else was misspelt or meant for another day
+    read: RefCell<BTreeSet<String>>,
+}
+
+impl Params {
+    pub fn from_pairs(pairs: &[(&str, &str)]) -> Params {
+        let mut params = Params::default();
+
+        for (key, value) in pairs.iter() {
+            params.set(key, value);
+        }
+
+        return params;
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    // the day's own default when the parameter wasn't given
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+        self.read.borrow_mut().insert(key.to_string());
+
+        match self.values.get(key) {
+            Some(value) => value.parse().map_err(|_| format!("invalid value for {}: {}", key, value)),
+            None => Ok(default),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.values.is_empty();
+    }
+
+    // every key and value, without counting them as asked for
+    pub fn pairs(&self) -> Vec<(&str, &str)> {
+        return self.values.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect();
+    }
+
+    // the given keys that nothing has asked for yet
+    pub fn unused(&self) -> Vec<&str> {
+        let read = self.read.borrow();
+
+        return self.values.keys().filter(|key| !read.contains(*key)).map(|key| key.as_str()).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_works() {
+        let params = Params::from_pairs(&[("workers", "2"), ("offset", "sixty")]);
+
+        assert_eq!(params.get("workers", 5), Ok(2));
+        assert_eq!(params.get("threshold", 10000), Ok(10000));
+        assert_eq!(params.get("offset", 60), Err(String::from("invalid value for offset: sixty")));
+    }
+
+    #[test]
+    fn unused_works() {
+        let params = Params::from_pairs(&[("workers", "2"), ("wrokers", "3")]);
+        assert_eq!(params.unused(), vec!["workers", "wrokers"]);
+
+        params.get("workers", 5).unwrap();
+        assert_eq!(params.unused(), vec!["wrokers"]);
+
+        assert_eq!(params.pairs(), vec![("workers", "2"), ("wrokers", "3")]);
+        assert_eq!(params.unused(), vec!["wrokers"]);
+    }
+}
@@ -0,0 +1,393 @@
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/*
+ * Small parser combinators for the puzzle inputs. A parser is anything that
+ * takes the Input and either moves past what it matched or fails. Failing
+ * doesn't say why on its own; the Input remembers what was expected at the
+ * furthest point any parser got to, which is where the error gets reported.
+ */
+pub struct Input<'a> {
+    text: &'a str,
+    offset: usize,
+    furthest: usize,
+    expected: Vec<String>,
+}
+
+// a parser didn't match, the details are kept on the Input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Failure;
+
+pub type Parsed<T> = Result<T, Failure>;
+
+pub trait Parser<T> {
+    fn parse(&self, input: &mut Input) -> Parsed<T>;
+}
+
+impl<T, F: Fn(&mut Input) -> Parsed<T>> Parser<T> for F {
+    fn parse(&self, input: &mut Input) -> Parsed<T> {
+        return self(input);
+    }
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Input<'a> {
+        Input { text, offset: 0, furthest: 0, expected: vec![] }
+    }
+
+    // what hasn't been matched yet
+    pub fn rest(&self) -> &'a str {
+        return &self.text[self.offset..];
+    }
+
+    pub fn offset(&self) -> usize {
+        return self.offset;
+    }
+
+    fn advance(&mut self, length: usize) {
+        self.offset += length;
+    }
+
+    fn reset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    // records that `expected` would have matched at `offset`, and fails
+    pub fn fail<T>(&mut self, offset: usize, expected: &str) -> Parsed<T> {
+        self.expect(offset, expected);
+        return Err(Failure);
+    }
+
+    // records that `expected` could also have matched at `offset`
+    pub fn expect(&mut self, offset: usize, expected: &str) {
+        if offset > self.furthest {
+            self.furthest = offset;
+            self.expected.clear();
+        }
+
+        if offset == self.furthest && !self.expected.iter().any(|other| other == expected) {
+            self.expected.push(expected.to_string());
+        }
+    }
+
+    // the furthest failure, as an error pointing into the line
+    pub fn error(&self) -> ParseError {
+        let expected = if self.expected.is_empty() { String::from("something else") } else { self.expected.join(" or ") };
+
+        return ParseError::new(&expected, self.text, self.furthest + 1);
+    }
+}
+
+// runs a parser over a whole line, which has to be used up completely
+pub fn parse_line<T, P: Parser<T>>(line: &str, parser: P) -> Result<T, ParseError> {
+    let mut input = Input::new(line);
+
+    match parser.parse(&mut input).and_then(|value| end().parse(&mut input).map(|_| value)) {
+        Ok(value) => Ok(value),
+        Err(Failure) => Err(input.error()),
+    }
+}
+
+pub fn literal(text: &'static str) -> impl Parser<()> {
+    return move |input: &mut Input| {
+        if input.rest().starts_with(text) {
+            input.advance(text.len());
+            return Ok(());
+        }
+
+        return input.fail(input.offset(), &format!("{:?}", text));
+    };
+}
+
+pub fn end() -> impl Parser<()> {
+    return |input: &mut Input| {
+        if input.rest().is_empty() {
+            return Ok(());
+        }
+
+        return input.fail(input.offset(), "the end of the line");
+    };
+}
+
+// one character that `matches` accepts, described by `expected` when it doesn't
+pub fn char_where(expected: &'static str, matches: fn(char) -> bool) -> impl Parser<char> {
+    return move |input: &mut Input| match input.rest().chars().next() {
+        Some(c) if matches(c) => {
+            input.advance(c.len_utf8());
+            Ok(c)
+        }
+        _ => input.fail(input.offset(), expected),
+    };
+}
+
+// the run of characters `matches` accepts, possibly none, without collecting them one at a time
+pub fn chars_while(expected: &'static str, matches: fn(char) -> bool) -> impl Parser<String> {
+    return move |input: &mut Input| {
+        let rest = input.rest();
+        let length = rest.find(|c| !matches(c)).unwrap_or(rest.len());
+
+        if length < rest.len() {
+            // whatever comes next might have wanted one more
+            input.expect(input.offset() + length, expected);
+        }
+
+        input.advance(length);
+        return Ok(rest[..length].to_string());
+    };
+}
+
+// one character out of `chars`, like the . and # of day 12's pots
+pub fn one_of(chars: &'static str) -> impl Parser<char> {
+    return move |input: &mut Input| match input.rest().chars().next() {
+        Some(c) if chars.contains(c) => {
+            input.advance(c.len_utf8());
+            Ok(c)
+        }
+        _ => {
+            let options: Vec<String> = chars.chars().map(|c| format!("{:?}", c)).collect();
+            input.fail(input.offset(), &options.join(" or "))
+        }
+    };
+}
+
+// any number of spaces or tabs, including none
+pub fn whitespace() -> impl Parser<()> {
+    return |input: &mut Input| {
+        let length = input.rest().len() - input.rest().trim_start_matches([' ', '\t']).len();
+        input.advance(length);
+
+        return Ok(());
+    };
+}
+
+fn number<T: FromStr>(input: &mut Input, sign: bool) -> Parsed<T> {
+    let start = input.offset();
+    let rest = input.rest();
+    let sign_length = if sign && (rest.starts_with('-') || rest.starts_with('+')) { 1 } else { 0 };
+    let digits = rest[sign_length..].chars().take_while(|c| c.is_ascii_digit()).count();
+
+    if digits == 0 {
+        return input.fail(start + sign_length, "a number");
+    }
+
+    // FromStr doesn't take a leading +
+    let text = &rest[..sign_length + digits];
+    match text.trim_start_matches('+').parse() {
+        Ok(value) => {
+            input.advance(text.len());
+            Ok(value)
+        }
+        Err(_) => input.fail(start, "a number that isn't so big"),
+    }
+}
+
+pub fn unsigned<T: FromStr>() -> impl Parser<T> {
+    return |input: &mut Input| number(input, false);
+}
+
+// a number with an optional - or + in front
+pub fn signed<T: FromStr>() -> impl Parser<T> {
+    return |input: &mut Input| number(input, true);
+}
+
+// skips whitespace before the parser, for day 10's "position=< 10585,  41558>" padding
+pub fn padded<T, P: Parser<T>>(parser: P) -> impl Parser<T> {
+    return move |input: &mut Input| {
+        whitespace().parse(input)?;
+        return parser.parse(input);
+    };
+}
+
+pub fn map<T, U, P: Parser<T>, F: Fn(T) -> U>(parser: P, f: F) -> impl Parser<U> {
+    return move |input: &mut Input| parser.parse(input).map(&f);
+}
+
+// the first of the two to match, starting from the same place
+pub fn alt<T, A: Parser<T>, B: Parser<T>>(first: A, second: B) -> impl Parser<T> {
+    return move |input: &mut Input| {
+        let start = input.offset();
+
+        if let Ok(value) = first.parse(input) {
+            return Ok(value);
+        }
+
+        input.reset(start);
+        return second.parse(input);
+    };
+}
+
+pub fn optional<T, P: Parser<T>>(parser: P) -> impl Parser<Option<T>> {
+    return move |input: &mut Input| {
+        let start = input.offset();
+
+        match parser.parse(input) {
+            Ok(value) => Ok(Some(value)),
+            Err(Failure) => {
+                input.reset(start);
+                Ok(None)
+            }
+        }
+    };
+}
+
+// as many matches as there are, possibly none
+pub fn many<T, P: Parser<T>>(parser: P) -> impl Parser<Vec<T>> {
+    return move |input: &mut Input| {
+        let mut values = vec![];
+
+        loop {
+            let start = input.offset();
+
+            match parser.parse(input) {
+                // a parser that matches nothing would match forever
+                Ok(value) if input.offset() > start => values.push(value),
+                Ok(_) => return Ok(values),
+                Err(Failure) => {
+                    input.reset(start);
+                    return Ok(values);
+                }
+            }
+        }
+    };
+}
+
+pub fn many1<T, P: Parser<T>>(parser: P) -> impl Parser<Vec<T>> {
+    let parser = many(parser);
+
+    return move |input: &mut Input| {
+        let start = input.offset();
+        let values = parser.parse(input)?;
+
+        if values.is_empty() {
+            // the failed attempt has already said what it wanted
+            input.reset(start);
+            return Err(Failure);
+        }
+
+        return Ok(values);
+    };
+}
+
+// exactly `count` matches in a row
+pub fn repeat<T, P: Parser<T>>(count: usize, parser: P) -> impl Parser<Vec<T>> {
+    return move |input: &mut Input| (0..count).map(|_| parser.parse(input)).collect();
+}
+
+// one or more matches with a separator between each of them
+pub fn separated<T, S, P: Parser<T>, Q: Parser<S>>(parser: P, separator: Q) -> impl Parser<Vec<T>> {
+    return move |input: &mut Input| {
+        let mut values = vec![parser.parse(input)?];
+
+        loop {
+            let start = input.offset();
+
+            if separator.parse(input).is_err() {
+                input.reset(start);
+                return Ok(values);
+            }
+
+            values.push(parser.parse(input)?);
+        }
+    };
+}
+
+// where the parser's match starts, as a 1 based column
+pub fn spanned<T, P: Parser<T>>(parser: P) -> impl Parser<(usize, T)> {
+    return move |input: &mut Input| {
+        let column = input.offset() + 1;
+        return parser.parse(input).map(|value| (column, value));
+    };
+}
+
+// replaces whatever the parser would have said it expected with one description
+pub fn label<T, P: Parser<T>>(expected: &'static str, parser: P) -> impl Parser<T> {
+    return move |input: &mut Input| {
+        let start = input.offset();
+        let (furthest, previous) = (input.furthest, input.expected.clone());
+
+        match parser.parse(input) {
+            Ok(value) => Ok(value),
+            Err(Failure) => {
+                input.furthest = furthest;
+                input.expected = previous;
+                input.reset(start);
+                input.fail(start, expected)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(input: &mut Input) -> Parsed<(u16, u16, u16)> {
+        literal("#").parse(input)?;
+        let id = unsigned().parse(input)?;
+        literal(" @ ").parse(input)?;
+        let left = unsigned().parse(input)?;
+        literal(",").parse(input)?;
+        let top = unsigned().parse(input)?;
+
+        return Ok((id, left, top));
+    }
+
+    #[test]
+    fn sequences_work() {
+        assert_eq!(parse_line("#1 @ 483,830", claim), Ok((1, 483, 830)));
+
+        let error = parse_line("#1 @ 483;830", claim).unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (9, "\",\""));
+
+        let error = parse_line("#1 @ 483,830x", claim).unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (13, "the end of the line"));
+    }
+
+    #[test]
+    fn numbers_work() {
+        assert_eq!(parse_line("-450", signed::<i64>()), Ok(-450));
+        assert_eq!(parse_line("+5", signed::<i64>()), Ok(5));
+        assert_eq!(parse_line("+", signed::<i64>()).unwrap_err().column, 2);
+        assert_eq!(parse_line("-5", unsigned::<u32>()).unwrap_err().column, 1);
+        assert_eq!(parse_line("70000", unsigned::<u16>()).unwrap_err().expected, "a number that isn't so big");
+        assert_eq!(parse_line("<  -3>", |input: &mut Input| {
+            literal("<").parse(input)?;
+            let value = padded(signed::<i64>()).parse(input)?;
+            literal(">").parse(input)?;
+            Ok(value)
+        }), Ok(-3));
+    }
+
+    #[test]
+    fn alternatives_work() {
+        let event = || alt(map(literal("wakes up"), |_| 1), alt(map(literal("falls asleep"), |_| 2), map(unsigned::<u32>(), |id| id * 10)));
+
+        assert_eq!(parse_line("falls asleep", event()), Ok(2));
+        assert_eq!(parse_line("7", event()), Ok(70));
+
+        let error = parse_line("eats lunch", event()).unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (1, "\"wakes up\" or \"falls asleep\" or a number"));
+        assert_eq!(parse_line("eats lunch", label("an event", event())).unwrap_err().expected, "an event");
+    }
+
+    #[test]
+    fn repetition_works() {
+        assert_eq!(parse_line("#..#", many(one_of(".#"))).unwrap().len(), 4);
+        assert_eq!(parse_line("", many(one_of(".#"))), Ok(vec![]));
+        assert_eq!(parse_line("", many1(one_of(".#"))).unwrap_err().expected, "'.' or '#'");
+        assert_eq!(parse_line("abc", chars_while("a letter", |c| c.is_ascii_lowercase())), Ok(String::from("abc")));
+        assert_eq!(parse_line("ab1", chars_while("a letter", |c| c.is_ascii_lowercase())).unwrap_err().expected, "a letter or the end of the line");
+        assert_eq!(parse_line("..x", repeat(3, one_of(".#"))).unwrap_err().column, 3);
+        assert_eq!(parse_line("1  2 3", separated(unsigned::<u8>(), many1(literal(" ")))), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_line("1  2", separated(spanned(unsigned::<u8>()), many1(literal(" ")))), Ok(vec![(1, 1), (4, 2)]));
+
+        let maybe_a = |input: &mut Input| {
+            let a = optional(literal("a")).parse(input)?;
+            literal("b").parse(input)?;
+            Ok(a.is_some())
+        };
+        assert_eq!(parse_line("b", maybe_a), Ok(false));
+        assert_eq!(parse_line("ab", maybe_a), Ok(true));
+    }
+}
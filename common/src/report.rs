@@ -0,0 +1,182 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::cancel::{self, Token};
+use crate::trace::{self, Trace};
+use crate::{json, Answer, Solution};
+
+// what a part that ran out of time reports instead of an answer
+pub const TIMEOUT: &str = "TIMEOUT";
+
+// what a part that panicked reports, followed by the panic's message when there is one
+pub const PANICKED: &str = "panicked";
+
+/*
+ * What happened when one part of one day was run. A part that couldn't run
+ * (no input, a parse error, a panic) keeps the reason instead of an answer.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Result<Answer, String>,
+    pub elapsed: Option<Duration>,
+}
+
+impl PartResult {
+    // times a single part, turning a panic, running past the budget or finding no answer into a failed result
+    pub fn run<F: FnOnce() -> Answer>(day: u8, part: u8, budget: Option<Duration>, f: F) -> PartResult {
+        let token = budget.map(Token::with_budget).unwrap_or_default();
+        let start = Instant::now();
+
+        match panic::catch_unwind(AssertUnwindSafe(|| cancel::within(&token, f))) {
+            Ok(Ok(Answer::Unsolved(reason))) => PartResult { day, part, outcome: Err(reason), elapsed: Some(start.elapsed()) },
+            Ok(Ok(answer)) => PartResult { day, part, outcome: Ok(answer), elapsed: Some(start.elapsed()) },
+            Ok(Err(_)) => PartResult { day, part, outcome: Err(TIMEOUT.to_string()), elapsed: Some(start.elapsed()) },
+            Err(payload) => PartResult::failed(day, part, &panicked(&*payload)),
+        }
+    }
+
+    pub fn failed(day: u8, part: u8, reason: &str) -> PartResult {
+        return PartResult { day, part, outcome: Err(reason.to_string()), elapsed: None };
+    }
+
+    // the answer as it would be printed, or why there isn't one
+    pub fn text(&self) -> String {
+        match &self.outcome {
+            Ok(answer) => answer.to_string(),
+            Err(reason) => reason.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        return json::object(&self.json_fields());
+    }
+
+    // the fields of to_json's object, for reports that add their own
+    pub fn json_fields(&self) -> Vec<(&'static str, String)> {
+        let elapsed = match self.elapsed {
+            Some(elapsed) => elapsed.as_nanos().to_string(),
+            None => String::from("null"),
+        };
+
+        let mut fields = vec![("day", self.day.to_string()), ("part", self.part.to_string())];
+
+        match &self.outcome {
+            Ok(answer) => {
+                fields.push(("answer", answer.to_json()));
+                fields.push(("answer_type", json::string(answer.type_name())));
+            }
+            Err(reason) => {
+                fields.push(("answer", String::from("null")));
+                fields.push(("answer_type", String::from("null")));
+                fields.push(("error", json::string(reason)));
+            }
+        }
+
+        fields.push(("elapsed_ns", elapsed));
+
+        return fields;
+    }
+}
+
+// each part gets the whole budget to itself, and writes its steps to `trace` when there is one
+pub fn solve_parts(day: u8, solution: &dyn Solution, budget: Option<Duration>, trace: Option<&Trace>) -> Vec<PartResult> {
+    let traced = |part: u8, f: &dyn Fn() -> Answer| match trace {
+        Some(trace) => trace::within(trace, day, part, f),
+        None => f(),
+    };
+
+    return vec![
+        PartResult::run(day, 1, budget, || traced(1, &|| solution.part_1())),
+        PartResult::run(day, 2, budget, || traced(2, &|| solution.part_2())),
+    ];
+}
+
+// a catch_unwind payload as "panicked: <message>", which panic! leaves as a &str or a String
+pub fn panicked(payload: &(dyn Any + Send)) -> String {
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => Some(message.to_string()),
+        None => payload.downcast_ref::<String>().cloned(),
+    };
+
+    match message {
+        Some(message) => format!("{}: {}", PANICKED, message),
+        None => PANICKED.to_string(),
+    }
+}
+
+// one result per line so the document stays readable in a terminal
+pub fn to_json(results: &[PartResult]) -> String {
+    let results: Vec<String> = results.iter().map(|result| format!("    {}", result.to_json())).collect();
+
+    if results.is_empty() {
+        return String::from("{\n  \"results\": []\n}");
+    }
+
+    return format!("{{\n  \"results\": [\n{}\n  ]\n}}", results.join(",\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_works() {
+        let answered = PartResult {
+            day: 7,
+            part: 2,
+            outcome: Ok(Answer::Integer(1081)),
+            elapsed: Some(Duration::from_nanos(1500)),
+        };
+        assert_eq!(
+            answered.to_json(),
+            "{\"day\": 7, \"part\": 2, \"answer\": 1081, \"answer_type\": \"integer\", \"elapsed_ns\": 1500}"
+        );
+
+        let failed = PartResult::failed(3, 1, "parse error");
+        assert_eq!(
+            failed.to_json(),
+            "{\"day\": 3, \"part\": 1, \"answer\": null, \"answer_type\": null, \"error\": \"parse error\", \"elapsed_ns\": null}"
+        );
+
+        assert_eq!(
+            to_json(&[answered, failed]).lines().collect::<Vec<&str>>()[..3],
+            ["{", "  \"results\": [", "    {\"day\": 7, \"part\": 2, \"answer\": 1081, \"answer_type\": \"integer\", \"elapsed_ns\": 1500},"]
+        );
+        assert_eq!(to_json(&[]), "{\n  \"results\": []\n}");
+    }
+
+    #[test]
+    fn run_catches_panics() {
+        let result = PartResult::run(1, 1, None, || panic!("boom"));
+        assert_eq!(result.outcome, Err(String::from("panicked: boom")));
+
+        let result = PartResult::run(1, 2, None, || panic!("{} is too many", 3));
+        assert_eq!(result.outcome, Err(String::from("panicked: 3 is too many")));
+
+        let result = PartResult::run(1, 2, None, || panic::panic_any(7));
+        assert_eq!(result.outcome, Err(String::from("panicked")));
+    }
+
+    #[test]
+    fn run_reports_unsolved() {
+        let result = PartResult::run(10, 1, None, || Answer::Unsolved(String::from("the points never spell a word")));
+        assert_eq!(result.outcome, Err(String::from("the points never spell a word")));
+        assert!(result.elapsed.is_some());
+    }
+
+    #[test]
+    fn run_times_out() {
+        let result = PartResult::run(10, 1, Some(Duration::from_millis(10)), || {
+            while !cancel::requested() {}
+            Answer::from("gave up")
+        });
+        assert_eq!(result.text(), TIMEOUT);
+        assert!(result.elapsed.is_some());
+
+        let result = PartResult::run(10, 1, Some(Duration::from_secs(60)), || Answer::from(3));
+        assert_eq!(result.outcome, Ok(Answer::Integer(3)));
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * A small seeded generator (SplitMix64) so generated puzzle inputs can be
+ * reproduced from nothing but the seed, without depending on a rand crate.
+ */
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        return z ^ (z >> 31);
+    }
+
+    // a number in 0..bound, bound must be greater than 0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        return ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64;
+    }
+
+    // a number between low and high, both included
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        return low + self.below((high - low) as u64 + 1) as i64;
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+
+        return unit < probability;
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.below(items.len() as u64) as usize];
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+
+        let first: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..5).map(|_| b.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(first, (0..5).map(|_| c.next_u64()).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn range_works() {
+        let mut rng = Rng::new(1);
+        let values: Vec<i64> = (0..1000).map(|_| rng.range(-3, 3)).collect();
+
+        assert!(values.iter().all(|value| (-3..=3).contains(value)));
+        assert!(values.contains(&-3) && values.contains(&3));
+    }
+
+    #[test]
+    fn shuffle_works() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,60 @@
+use grid::Image;
+
+use crate::snapshot::Snapshot;
+use crate::stepper::Simulation;
+use crate::{Answer, Params, ParseError};
+
+/*
+ * Each day parses its input once and then answers both parts from the parsed
+ * form, so the runner can time and call every day the same way.
+ */
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    // only days with tunable constants need to look at these
+    fn configure(&mut self, _params: &Params) -> Result<(), String> {
+        return Ok(());
+    }
+
+    // whether the configured puzzle is small enough to solve for anyone who asks, checked before
+    // the server takes it on since a tiny input can still ask for a huge grid or game
+    fn check_size(&self) -> Result<(), String> {
+        return Ok(());
+    }
+
+    fn part_1(&self) -> Answer;
+
+    fn part_2(&self) -> Answer;
+
+    // a picture of the parsed puzzle, for the days where looking at the data helps
+    fn render(&self) -> Option<Image> {
+        return None;
+    }
+
+    // a fresh run of the puzzle's simulation to step through, for the days that are one
+    fn simulate(&self) -> Option<Box<dyn Simulation + '_>> {
+        return None;
+    }
+
+    // the simulation carried on from a snapshot it saved earlier
+    fn resume(&self, _snapshot: &Snapshot) -> Result<Box<dyn Simulation + '_>, String> {
+        return Err(String::from("there's no simulation to resume"));
+    }
+}
+
+// configures a day, treating any parameter it didn't ask for as a mistake
+pub fn configure(solution: &mut dyn Solution, params: &Params) -> Result<(), String> {
+    solution.configure(params)?;
+
+    match params.unused().as_slice() {
+        [] => Ok(()),
+        unused => Err(format!("unknown parameter: {}", unused.join(", "))),
+    }
+}
+
+// lets a registry of days hold `boxed::<day_7::Puzzle>` as a plain fn pointer
+pub fn boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, ParseError> {
+    return S::parse(input).map(|solution| Box::new(solution) as Box<dyn Solution>);
+}
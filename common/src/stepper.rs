@@ -0,0 +1,406 @@
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use crate::cancel::Token;
+use crate::snapshot::Snapshot;
+
+use crate::parser::{alt, chars_while, literal, map, padded, parse_line, signed, Input, Parsed, Parser};
+use crate::ParseError;
+
+/*
+ * A simulation that can be watched one step at a time. Steps only go
+ * forwards, going back starts a fresh simulation and replays it to the
+ * earlier tick, so a simulation doesn't have to remember where it's been.
+ */
+pub trait Simulation {
+    // how many steps have been taken
+    fn tick(&self) -> u64;
+
+    // takes one step, false once there's nothing left to simulate
+    fn step(&mut self) -> bool;
+
+    // the state as lines of text, drawn to fit `width` columns
+    fn view(&self, width: usize) -> Vec<String>;
+
+    // the numbers `until` conditions can test, besides the tick
+    fn metrics(&self) -> Vec<(&'static str, i64)>;
+
+    // everything needed to carry on from this tick, see Solution::resume
+    fn snapshot(&self) -> Snapshot;
+}
+
+// run-until gives up after this long rather than hanging the terminal
+pub const RUN_BUDGET: Duration = Duration::from_secs(10);
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const BOLD: &str = "\x1b[1m";
+const REVERSE: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+const HELP: &str = "enter repeats the last command
+  s [n]      step forwards one or n steps
+  b [n]      step back one or n steps
+  j <tick>   jump to a tick
+  u <cond>   run until a condition holds, like height<10 or tick>=500
+  w <path>   save a snapshot to carry on from later with --resume
+  q          quit";
+
+// stands out in a view, like the current marble
+pub fn highlight(text: &str) -> String {
+    return format!("{}{}{}", REVERSE, text, RESET);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+// a metric compared with a number, like "height<10"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    metric: String,
+    comparison: Comparison,
+    value: i64,
+}
+
+impl Condition {
+    pub fn parse(text: &str) -> Result<Condition, ParseError> {
+        return parse_line(text.trim(), condition);
+    }
+
+    // None when the simulation has no metric by that name
+    fn holds(&self, simulation: &dyn Simulation) -> Option<bool> {
+        let actual = metric(simulation, &self.metric)?;
+
+        let holds = match self.comparison {
+            Comparison::Less => actual < self.value,
+            Comparison::LessOrEqual => actual <= self.value,
+            Comparison::Equal => actual == self.value,
+            Comparison::NotEqual => actual != self.value,
+            Comparison::GreaterOrEqual => actual >= self.value,
+            Comparison::Greater => actual > self.value,
+        };
+
+        return Some(holds);
+    }
+}
+
+fn metric(simulation: &dyn Simulation, name: &str) -> Option<i64> {
+    if name == "tick" {
+        return Some(simulation.tick() as i64);
+    }
+
+    return simulation.metrics().into_iter().find(|&(metric, _)| metric == name).map(|(_, value)| value);
+}
+
+fn condition(input: &mut Input) -> Parsed<Condition> {
+    let start = input.offset();
+    let metric = chars_while("a metric", |c| c.is_ascii_alphanumeric() || c == '_').parse(input)?;
+    if metric.is_empty() {
+        return input.fail(start, "a metric like tick");
+    }
+
+    // the two character comparisons have to be tried before the one character ones
+    let comparison = padded(alt(
+        alt(
+            alt(map(literal("<="), |_| Comparison::LessOrEqual), map(literal(">="), |_| Comparison::GreaterOrEqual)),
+            alt(map(literal("!="), |_| Comparison::NotEqual), map(literal("="), |_| Comparison::Equal)),
+        ),
+        alt(map(literal("<"), |_| Comparison::Less), map(literal(">"), |_| Comparison::Greater)),
+    ))
+    .parse(input)?;
+
+    let value = padded(signed()).parse(input)?;
+
+    return Ok(Condition { metric, comparison, value });
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Step(u64),
+    Back(u64),
+    Jump(u64),
+    Until(Condition),
+    Save(String),
+    Help,
+    Quit,
+}
+
+impl Command {
+    fn parse(line: &str) -> Result<Command, String> {
+        let line = line.trim();
+        let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        let count = |default: Option<u64>| match (rest, default) {
+            ("", Some(default)) => Ok(default),
+            _ => rest.parse::<u64>().map_err(|_| format!("{} needs a number, got: {}", name, rest)),
+        };
+
+        match name {
+            "s" => Ok(Command::Step(count(Some(1))?)),
+            "b" => Ok(Command::Back(count(Some(1))?)),
+            "j" => Ok(Command::Jump(count(None)?)),
+            "u" => match Condition::parse(rest) {
+                Ok(condition) => Ok(Command::Until(condition)),
+                Err(e) => Err(format!("expected {} at column {} of the condition", e.expected, e.column)),
+            },
+            "w" if !rest.is_empty() => Ok(Command::Save(rest.to_string())),
+            "w" => Err(String::from("w needs a path to save the snapshot to")),
+            "?" | "h" => Ok(Command::Help),
+            "q" => Ok(Command::Quit),
+            _ => Err(format!("unknown command: {} (? for help)", line)),
+        }
+    }
+}
+
+pub struct Stepper<'a> {
+    start: Box<dyn Fn() -> Box<dyn Simulation + 'a> + 'a>,
+    current: Box<dyn Simulation + 'a>,
+    // the last step didn't go anywhere
+    finished: bool,
+}
+
+impl<'a> Stepper<'a> {
+    pub fn new<F: Fn() -> Box<dyn Simulation + 'a> + 'a>(start: F) -> Stepper<'a> {
+        let current = start();
+        return Stepper { start: Box::new(start), current, finished: false };
+    }
+
+    pub fn simulation(&self) -> &dyn Simulation {
+        return self.current.as_ref();
+    }
+
+    pub fn forward(&mut self, steps: u64) {
+        for _ in 0..steps {
+            if !self.current.step() {
+                self.finished = true;
+                return;
+            }
+        }
+    }
+
+    pub fn jump(&mut self, tick: u64) {
+        if tick < self.current.tick() {
+            self.current = (self.start)();
+            self.finished = false;
+        }
+
+        while self.current.tick() < tick && !self.finished {
+            self.forward(1);
+        }
+    }
+
+    pub fn back(&mut self, steps: u64) {
+        self.jump(self.current.tick().saturating_sub(steps));
+    }
+
+    // steps until the condition holds, saying why it stopped
+    pub fn run_until(&mut self, condition: &Condition) -> Result<String, String> {
+        if metric(self.simulation(), &condition.metric).is_none() {
+            return Err(format!("no metric called {}", condition.metric));
+        }
+
+        let token = Token::with_budget(RUN_BUDGET);
+
+        while !token.is_cancelled() {
+            self.forward(1);
+
+            if self.finished {
+                return Ok(String::from("the simulation ended first"));
+            }
+            if condition.holds(self.simulation()) == Some(true) {
+                return Ok(format!("stopped at tick {}", self.current.tick()));
+            }
+        }
+
+        return Ok(format!("gave up at tick {} after {}s", self.current.tick(), RUN_BUDGET.as_secs()));
+    }
+
+    fn draw(&self, output: &mut dyn Write, width: usize, message: &str) -> io::Result<()> {
+        let metrics: Vec<String> =
+            self.current.metrics().iter().map(|(name, value)| format!("{} {}", name, value)).collect();
+        let ended = if self.finished { "  (ended)" } else { "" };
+
+        write!(output, "{}", CLEAR)?;
+        writeln!(output, "{}tick {}{}  {}{}", BOLD, self.current.tick(), RESET, metrics.join("  "), ended)?;
+        writeln!(output)?;
+
+        for line in self.current.view(width) {
+            writeln!(output, "{}", line)?;
+        }
+
+        writeln!(output)?;
+        if !message.is_empty() {
+            writeln!(output, "{}", message)?;
+        }
+        write!(output, "> ")?;
+
+        return output.flush();
+    }
+}
+
+/*
+ * The interactive loop: draws the simulation, reads a command per line and
+ * carries it out, until `q` or the end of the input.
+ */
+pub fn run(stepper: &mut Stepper, input: &mut dyn BufRead, output: &mut dyn Write, width: usize) -> io::Result<()> {
+    let mut last = Command::Step(1);
+    let mut message = String::from("? for help");
+
+    loop {
+        stepper.draw(output, width, &message)?;
+        message.clear();
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+
+        let command = if line.trim().is_empty() { Ok(last.clone()) } else { Command::parse(&line) };
+
+        let command = match command {
+            Ok(command) => command,
+            Err(e) => {
+                message = e;
+                continue;
+            }
+        };
+
+        match &command {
+            Command::Step(steps) => stepper.forward(*steps),
+            Command::Back(steps) => stepper.back(*steps),
+            Command::Jump(tick) => stepper.jump(*tick),
+            Command::Until(condition) => match stepper.run_until(condition) {
+                Ok(stopped) => message = stopped,
+                Err(e) => message = e,
+            },
+            Command::Save(path) => match stepper.simulation().snapshot().save(path) {
+                Ok(()) => message = format!("saved tick {} to {}", stepper.simulation().tick(), path),
+                Err(e) => message = e,
+            },
+            Command::Help => message = HELP.to_string(),
+            Command::Quit => return Ok(()),
+        }
+
+        last = command;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts up by `by` every step, stopping at 100
+    struct Counter {
+        tick: u64,
+        by: i64,
+    }
+
+    impl Simulation for Counter {
+        fn tick(&self) -> u64 {
+            return self.tick;
+        }
+
+        fn step(&mut self) -> bool {
+            if self.tick == 100 {
+                return false;
+            }
+
+            self.tick += 1;
+            return true;
+        }
+
+        fn view(&self, _width: usize) -> Vec<String> {
+            return vec![format!("count is {}", self.tick as i64 * self.by)];
+        }
+
+        fn metrics(&self) -> Vec<(&'static str, i64)> {
+            return vec![("count", self.tick as i64 * self.by)];
+        }
+
+        fn snapshot(&self) -> Snapshot {
+            return Snapshot::new(0, self.tick).with("by", self.by);
+        }
+    }
+
+    fn stepper() -> Stepper<'static> {
+        return Stepper::new(|| Box::new(Counter { tick: 0, by: 3 }));
+    }
+
+    #[test]
+    fn stepping_works() {
+        let mut stepper = stepper();
+
+        stepper.forward(5);
+        assert_eq!(stepper.simulation().tick(), 5);
+
+        stepper.back(2);
+        assert_eq!(stepper.simulation().tick(), 3);
+
+        stepper.back(10);
+        assert_eq!(stepper.simulation().tick(), 0);
+
+        stepper.jump(500);
+        assert_eq!(stepper.simulation().tick(), 100);
+        assert!(stepper.finished);
+
+        stepper.jump(40);
+        assert_eq!((stepper.simulation().tick(), stepper.finished), (40, false));
+    }
+
+    #[test]
+    fn run_until_works() {
+        let mut stepper = stepper();
+
+        assert_eq!(stepper.run_until(&Condition::parse("count >= 30").unwrap()), Ok(String::from("stopped at tick 10")));
+        assert_eq!(stepper.simulation().tick(), 10);
+
+        assert!(stepper.run_until(&Condition::parse("size<3").unwrap()).is_err());
+        assert_eq!(stepper.run_until(&Condition::parse("count=-1").unwrap()), Ok(String::from("the simulation ended first")));
+    }
+
+    #[test]
+    fn condition_parse_works() {
+        let condition = Condition::parse("height<10").unwrap();
+        assert_eq!((condition.metric.as_str(), condition.comparison, condition.value), ("height", Comparison::Less, 10));
+
+        assert_eq!(Condition::parse("diff != -2").unwrap().comparison, Comparison::NotEqual);
+        assert_eq!(Condition::parse("tick>=5").unwrap().comparison, Comparison::GreaterOrEqual);
+        assert_eq!(Condition::parse("<10").unwrap_err().column, 1);
+        assert_eq!(Condition::parse("height 10").unwrap_err().column, 8);
+    }
+
+    #[test]
+    fn command_parse_works() {
+        assert_eq!(Command::parse("s"), Ok(Command::Step(1)));
+        assert_eq!(Command::parse("s 20\n"), Ok(Command::Step(20)));
+        assert_eq!(Command::parse("b 3"), Ok(Command::Back(3)));
+        assert_eq!(Command::parse("j 10000"), Ok(Command::Jump(10000)));
+        assert!(Command::parse("j").is_err());
+        assert!(Command::parse("u height").is_err());
+        assert_eq!(Command::parse("w pots.snapshot"), Ok(Command::Save(String::from("pots.snapshot"))));
+        assert!(Command::parse("w").is_err());
+        assert!(Command::parse("x").is_err());
+    }
+
+    #[test]
+    fn run_works() {
+        let mut stepper = stepper();
+        let mut output = vec![];
+
+        run(&mut stepper, &mut "s 4\n\nb\nu count>20\nq\n".as_bytes(), &mut output, 80).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        // enter repeats the s 4 and b goes back one, then the until takes at least one step
+        assert_eq!(stepper.simulation().tick(), 8);
+        assert!(output.contains("count is 21"));
+        assert!(output.contains("stopped at tick 8"));
+    }
+}
@@ -0,0 +1,184 @@
+use std::cell::{Cell, RefCell};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use crate::json;
+
+/*
+ * What a simulation looked like after one step, for following along instead
+ * of sprinkling println!s: a one line summary of the whole state and the
+ * items that changed during the step.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub summary: String,
+    pub changed: Vec<String>,
+}
+
+impl Event {
+    pub fn new(summary: String) -> Event {
+        Event { summary, changed: vec![] }
+    }
+
+    pub fn changed<T: ToString>(mut self, items: impl IntoIterator<Item = T>) -> Event {
+        self.changed = items.into_iter().map(|item| item.to_string()).collect();
+        return self;
+    }
+}
+
+/*
+ * Where events go, one JSON object per line, and which steps to keep. Clones
+ * share the same writer so every part of a run ends up in the one file.
+ */
+#[derive(Clone)]
+pub struct Trace {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    steps: Range<u64>,
+}
+
+struct Current {
+    trace: Trace,
+    day: u8,
+    part: u8,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Current>> = const { RefCell::new(None) };
+    // whether CURRENT is set, cheap enough to check on every step of a hot loop
+    static TRACING: Cell<bool> = const { Cell::new(false) };
+}
+
+impl Trace {
+    pub fn new(writer: Box<dyn Write + Send>, steps: Range<u64>) -> Trace {
+        Trace { writer: Arc::new(Mutex::new(writer)), steps }
+    }
+
+    pub fn to_file(path: &str, steps: Range<u64>) -> Result<Trace, String> {
+        let file = File::create(path).map_err(|e| format!("could not create trace file {}: {}", path, e))?;
+        return Ok(Trace::new(Box::new(BufWriter::new(file)), steps));
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        return self.writer.lock().unwrap().flush().map_err(|e| format!("could not write trace: {}", e));
+    }
+
+    fn write(&self, day: u8, part: u8, step: u64, event: Event) {
+        let changed: Vec<String> = event.changed.iter().map(|item| json::string(item)).collect();
+        let line = json::object(&[
+            ("day", day.to_string()),
+            ("part", part.to_string()),
+            ("step", step.to_string()),
+            ("summary", json::string(&event.summary)),
+            ("changed", format!("[{}]", changed.join(", "))),
+        ]);
+
+        // a trace that can't be written shouldn't stop the answer
+        writeln!(self.writer.lock().unwrap(), "{}", line).ok();
+    }
+}
+
+// "10..20", "10.." or "..20", the end not included like a Rust range
+pub fn parse_steps(text: &str) -> Result<Range<u64>, String> {
+    let error = || format!("expected a step range like 10..20, 10.. or ..20, got: {}", text);
+    let (from, to) = text.split_once("..").ok_or_else(error)?;
+
+    let from = if from.is_empty() { 0 } else { from.parse().map_err(|_| error())? };
+    let to = if to.is_empty() { u64::MAX } else { to.parse().map_err(|_| error())? };
+
+    if from >= to {
+        return Err(error());
+    }
+
+    return Ok(from..to);
+}
+
+// runs `f` with its events going to `trace`, labelled with the day and part
+pub fn within<T, F: FnOnce() -> T>(trace: &Trace, day: u8, part: u8, f: F) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(Current { trace: trace.clone(), day, part })));
+    TRACING.with(|tracing| tracing.set(true));
+
+    // put the previous trace back even if `f` panics
+    struct Restore(Option<Current>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            TRACING.with(|tracing| tracing.set(previous.is_some()));
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(previous);
+    return f();
+}
+
+/*
+ * Records what happened at `step`. The event is only built when something is
+ * tracing this step, so simulations can call this every step for free.
+ */
+#[inline]
+pub fn emit<F: FnOnce() -> Event>(step: u64, event: F) {
+    if !TRACING.with(Cell::get) {
+        return;
+    }
+
+    CURRENT.with(|current| {
+        if let Some(current) = current.borrow().as_ref() {
+            if current.trace.steps.contains(&step) {
+                current.trace.write(current.day, current.part, step, event());
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a writer the test can read back
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            return Ok(buf.len());
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            return Ok(());
+        }
+    }
+
+    #[test]
+    fn emit_works() {
+        let written = Shared::default();
+        let trace = Trace::new(Box::new(written.clone()), 1..3);
+
+        emit(1, || panic!("nothing is tracing yet"));
+
+        within(&trace, 12, 2, || {
+            for step in 0..5 {
+                emit(step, || Event::new(format!("total {}", step * 10)).changed(vec![step as i64, -(step as i64)]));
+            }
+        });
+
+        let text = String::from_utf8(written.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            text,
+            "{\"day\": 12, \"part\": 2, \"step\": 1, \"summary\": \"total 10\", \"changed\": [\"1\", \"-1\"]}\n\
+             {\"day\": 12, \"part\": 2, \"step\": 2, \"summary\": \"total 20\", \"changed\": [\"2\", \"-2\"]}\n"
+        );
+    }
+
+    #[test]
+    fn parse_steps_works() {
+        assert_eq!(parse_steps("10..20"), Ok(10..20));
+        assert_eq!(parse_steps("10.."), Ok(10..u64::MAX));
+        assert_eq!(parse_steps("..20"), Ok(0..20));
+        assert!(parse_steps("20..10").is_err());
+        assert!(parse_steps("10").is_err());
+        assert!(parse_steps("a..b").is_err());
+    }
+}
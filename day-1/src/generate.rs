@@ -0,0 +1,45 @@
+use common::rng::Rng;
+
+/// How many frequency changes `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// `size` frequency changes. The last change brings the total back to 0, so
+/// the starting frequency is reached again and part 2 always finishes.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut changes = vec![];
+    let mut total = 0;
+
+    for _i in 1..size.max(2) {
+        let change = match rng.range(-100_000, 100_000) {
+            0 => 1,
+            change => change,
+        };
+
+        total += change;
+        changes.push(change);
+    }
+
+    changes.push(-total);
+
+    let lines: Vec<String> = changes.iter().map(|change| format!("{:+}", change)).collect();
+
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 50);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.lines().count(), 50);
+            assert_eq!(puzzle.part_1().to_string(), "0");
+            puzzle.part_2();
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Day 1: Chronal Calibration. The input is a list of frequency changes,
+//! part 1 adds them all up and part 2 finds the first frequency reached twice
+//! while the list repeats.
+//!
+//! ```
+//! use common::Solution;
+//!
+//! let puzzle = day_1::Puzzle::parse("+1\n-2\n+3\n+1").unwrap();
+//! assert_eq!(puzzle.part_1().to_string(), "3");
+//! assert_eq!(puzzle.part_2().to_string(), "2");
+//! ```
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+use common::cancel;
+use common::parser::{parse_line, signed};
+use common::{parse_lines, Answer, Example, ParseError, Solution};
+
+/// Frequency changes that add back up to 0, so part 2 always has an answer.
+pub mod generate;
+/// Part 2 goes round the changes keeping every frequency seen, without checking first that one repeats.
+pub mod reference;
+
+/// The frequency changes, in order.
+pub struct Puzzle {
+    changes: Vec<i64>,
+}
+
+/// The puzzle text's lists of changes and where they end up.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "+1, -2, +3, +1",
+        input: "+1\n\
+                -2\n\
+                +3\n\
+                +1",
+        params: &[],
+        part_1: Some("3"),
+        part_2: Some("2"),
+    },
+    Example {
+        name: "+1, +1, +1",
+        input: "+1\n\
+                +1\n\
+                +1",
+        params: &[],
+        part_1: Some("3"),
+        part_2: None,
+    },
+    Example {
+        name: "+1, +1, -2",
+        input: "+1\n\
+                +1\n\
+                -2",
+        params: &[],
+        part_1: Some("0"),
+        part_2: None,
+    },
+    Example {
+        name: "-1, -2, -3",
+        input: "-1\n\
+                -2\n\
+                -3",
+        params: &[],
+        part_1: Some("-6"),
+        part_2: None,
+    },
+    Example {
+        name: "+1, -1",
+        input: "+1\n\
+                -1",
+        params: &[],
+        part_1: None,
+        part_2: Some("0"),
+    },
+    Example {
+        name: "+3, +3, +4, -2, -4",
+        input: "+3\n\
+                +3\n\
+                +4\n\
+                -2\n\
+                -4",
+        params: &[],
+        part_1: None,
+        part_2: Some("10"),
+    },
+    Example {
+        name: "-6, +3, +8, +5, -6",
+        input: "-6\n\
+                +3\n\
+                +8\n\
+                +5\n\
+                -6",
+        params: &[],
+        part_1: None,
+        part_2: Some("5"),
+    },
+    Example {
+        name: "+7, +7, -2, -7, -4",
+        input: "+7\n\
+                +7\n\
+                -2\n\
+                -7\n\
+                -4",
+        params: &[],
+        part_1: None,
+        part_2: Some("14"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle { changes: parse_lines(input, convert_line_to_number)? })
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut count: i64 = 0;
+
+        for change in self.changes.iter() {
+            count += change;
+        }
+
+        return Answer::from(count);
+    }
+
+    fn part_2(&self) -> Answer {
+        if never_repeats(&self.changes) {
+            return Answer::Unsolved(String::from("no frequency is ever reached twice"));
+        }
+
+        let mut seen = HashSet::new();
+        let mut count: i64 = 0;
+
+        seen.insert(count);
+
+        // the list of changes repeats until a frequency is reached twice, which can take a lot of passes
+        while !cancel::requested() {
+            for change in self.changes.iter() {
+                count += change;
+
+                if !seen.insert(count) {
+                    return Answer::from(count);
+                }
+            }
+        }
+
+        return Answer::Unsolved(String::from("gave up before a frequency was reached twice"));
+    }
+}
+
+/*
+ * Every pass through the list shifts the frequencies of the first pass by the
+ * total change, so two frequencies can only ever meet if the first pass hits
+ * the same one twice or two of them are a multiple of the total apart.
+ */
+fn never_repeats(changes: &[i64]) -> bool {
+    let total: i64 = changes.iter().sum();
+    if total == 0 {
+        return changes.is_empty();
+    }
+
+    let mut remainders = HashSet::new();
+    let mut frequency: i64 = 0;
+
+    for change in changes.iter() {
+        if !remainders.insert(frequency.rem_euclid(total)) {
+            return false;
+        }
+
+        frequency += change;
+    }
+
+    return true;
+}
+
+/// One frequency change, which is either:
+///   - a positive integer: +5
+///   - a negative integer: -450
+pub fn convert_line_to_number(line: &str) -> Result<i64, ParseError> {
+    return parse_line(line, signed());
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::report::{self, PartResult};
+    use std::time::Duration;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn it_works() {
+        assert_eq!(convert_line_to_number("+4"), Ok(4));
+        assert_eq!(convert_line_to_number("-4"), Ok(-4));
+        assert_eq!(convert_line_to_number("0"), Ok(0));
+    }
+
+    #[test]
+    fn convert_line_to_number_errors() {
+        assert_eq!(convert_line_to_number("+4x").unwrap_err().column, 3);
+        assert_eq!(convert_line_to_number("+").unwrap_err().column, 2);
+        assert_eq!(convert_line_to_number("").unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn part_2_works() {
+        assert_eq!(Puzzle::parse("+1\n-1").unwrap().part_2(), Answer::Integer(0));
+        assert_eq!(Puzzle::parse("+3\n+3\n+4\n-2\n-4").unwrap().part_2(), Answer::Integer(10));
+    }
+
+    #[test]
+    fn never_repeating_is_unsolved() {
+        let unsolved = Answer::Unsolved(String::from("no frequency is ever reached twice"));
+
+        assert_eq!(Puzzle::parse("+1\n+1").unwrap().part_2(), unsolved);
+        assert_eq!(Puzzle::parse("+1").unwrap().part_2(), unsolved);
+        assert_eq!(Puzzle::parse("+2\n+2\n+1").unwrap().part_2(), unsolved);
+        assert_eq!(Puzzle { changes: vec![] }.part_2(), unsolved);
+
+        // 2 and 5 are the total of 3 apart, so 5 comes round again on the second pass
+        assert_eq!(Puzzle::parse("+2\n+3\n-2").unwrap().part_2(), Answer::Integer(5));
+    }
+
+    #[test]
+    fn part_2_can_be_cancelled() {
+        // this does repeat, but only after ten million passes
+        let puzzle = Puzzle::parse("+10000000\n-9999999").unwrap();
+        let result = PartResult::run(1, 2, Some(Duration::from_millis(50)), || puzzle.part_2());
+
+        assert_eq!(result.text(), report::TIMEOUT);
+    }
+}
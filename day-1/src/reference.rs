@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given generated lists, which always repeat, of up to 2000 changes.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 2000 };
+
+fn changes(input: &str) -> Vec<i64> {
+    return input.lines().map(|line| line.trim_start_matches('+').parse().unwrap()).collect();
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    return Answer::from(changes(input).iter().sum::<i64>());
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    let mut frequency = 0;
+    let mut seen = HashSet::new();
+    seen.insert(frequency);
+
+    for change in changes(input).iter().cycle() {
+        frequency += change;
+
+        if !seen.insert(frequency) {
+            return Answer::from(frequency);
+        }
+    }
+
+    unreachable!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
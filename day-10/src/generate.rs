@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use common::rng::Rng;
+
+/// Roughly how many points of light `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 300;
+
+const MESSAGE_HEIGHT: i64 = 10;
+
+/// About `size` points that line up into a message of strokes some number of
+/// seconds from now. Strokes are at least two points long so that every point
+/// touches another one, which is how the solver knows the message appeared.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let message = draw_message(rng, size.max(2));
+    let seconds = rng.range(10, 10_000);
+    let mut points = vec![];
+
+    for (x, y) in message {
+        let velocity_x = rng.range(-5, 5);
+        let velocity_y = rng.range(-5, 5);
+
+        points.push(format!(
+            "position=<{:6}, {:6}> velocity=<{:2}, {:2}>",
+            x - velocity_x * seconds,
+            y - velocity_y * seconds,
+            velocity_x,
+            velocity_y
+        ));
+    }
+
+    rng.shuffle(&mut points);
+
+    return points.join("\n");
+}
+
+fn draw_message(rng: &mut Rng, size: usize) -> HashSet<(i64, i64)> {
+    let width = (size as i64 / 4).max(MESSAGE_HEIGHT);
+    let mut pixels = HashSet::new();
+
+    while pixels.len() < size {
+        let length = rng.range(2, 6);
+        let (mut x, mut y) = (rng.range(0, width - 1), rng.range(0, MESSAGE_HEIGHT - 1));
+        let horizontal = rng.chance(0.5);
+
+        for _i in 0..length {
+            pixels.insert((x, y));
+
+            if horizontal && x < width - 1 {
+                x += 1;
+            } else if y < MESSAGE_HEIGHT - 1 {
+                y += 1;
+            } else if x < width - 1 {
+                x += 1;
+            } else {
+                // stuck in the bottom right corner, a stroke of one point wouldn't touch anything
+                x -= 1;
+            }
+        }
+    }
+
+    return pixels;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..3 {
+            let input = generate(&mut Rng::new(seed), 40);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert!(input.lines().count() >= 40);
+            assert!(puzzle.part_1().to_string().lines().count() <= MESSAGE_HEIGHT as usize);
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! Day 10: The Stars Align. Points of light drift across the sky until, for
+//! one second, they spell a message. Part 1 is the message and part 2 how many
+//! seconds it takes to appear.
+//!
+//! ```
+//! let stars: Vec<day_10::Star> = ["position=< 0, 0> velocity=< 1, 0>", "position=< 3, 0> velocity=<-1, 0>"]
+//!     .iter()
+//!     .map(|line| day_10::convert_line_to_variables(line).unwrap())
+//!     .collect();
+//!
+//! let (seconds, sky) = day_10::find_message(stars).unwrap();
+//! assert_eq!((seconds, day_10::render_sky(&sky)), (1, vec![String::from("##")]));
+//! ```
+
+#![warn(missing_docs)]
+
+use common::cancel;
+use common::parser::{literal, padded, parse_line, signed, whitespace, Input, Parsed, Parser};
+use common::snapshot::Snapshot;
+use common::stepper::Simulation;
+use common::trace::{self, Event};
+use common::{parse_lines, Answer, Example, ParseError, Solution};
+use grid::image::Rgb;
+use grid::{Bounds, Image, Point, SparseGrid};
+
+/// Points of light that line up into strokes of a message.
+pub mod generate;
+/// Tries every second up to the latest a generated message appears, and picks the one with the smallest box.
+pub mod reference;
+
+/// A point of light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Star {
+    /// Where the star is now.
+    pub position: Point,
+    /// How far the star moves every second.
+    pub velocity: Point,
+}
+
+// the points converge well before this many seconds have passed
+const MAX_SECONDS: usize = 10400;
+
+// the biggest sky part 1 will draw, the real message is 62 by 10
+const MAX_MESSAGE_WIDTH: usize = 1000;
+const MAX_MESSAGE_HEIGHT: usize = 100;
+
+// how big a square each star is drawn as by --render
+const PIXELS_PER_STAR: usize = 8;
+
+// aoc step only draws the sky once it's no taller than this
+const MAX_VIEW_ROWS: usize = 40;
+
+/// Every star, where they start out.
+pub struct Puzzle {
+    stars: Vec<Star>,
+}
+
+/// The puzzle text's stars, which spell HI after three seconds.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "message",
+        input: "position=< 9,  1> velocity=< 0,  2>\n\
+                position=< 7,  0> velocity=<-1,  0>\n\
+                position=< 3, -2> velocity=<-1,  1>\n\
+                position=< 6, 10> velocity=<-2, -1>\n\
+                position=< 2, -4> velocity=< 2,  2>\n\
+                position=<-6, 10> velocity=< 2, -2>\n\
+                position=< 1,  8> velocity=< 1, -1>\n\
+                position=< 1,  7> velocity=< 1,  0>\n\
+                position=<-3, 11> velocity=< 1, -2>\n\
+                position=< 7,  6> velocity=<-1, -1>\n\
+                position=<-2,  3> velocity=< 1,  0>\n\
+                position=<-4,  3> velocity=< 2,  0>\n\
+                position=<10, -3> velocity=<-1,  1>\n\
+                position=< 5, 11> velocity=< 1, -2>\n\
+                position=< 4,  7> velocity=< 0, -1>\n\
+                position=< 8, -2> velocity=< 0,  1>\n\
+                position=<15,  0> velocity=<-2,  0>\n\
+                position=< 1,  6> velocity=< 1,  0>\n\
+                position=< 8,  9> velocity=< 0, -1>\n\
+                position=< 3,  3> velocity=<-1,  1>\n\
+                position=< 0,  5> velocity=< 0, -1>\n\
+                position=<-2,  2> velocity=< 2,  0>\n\
+                position=< 5, -2> velocity=< 1,  2>\n\
+                position=< 1,  4> velocity=< 2,  1>\n\
+                position=<-2,  7> velocity=< 2, -2>\n\
+                position=< 3,  6> velocity=<-1, -1>\n\
+                position=< 5,  0> velocity=< 1,  0>\n\
+                position=<-6,  0> velocity=< 2,  0>\n\
+                position=< 5,  9> velocity=< 1, -2>\n\
+                position=<14,  7> velocity=<-2,  0>\n\
+                position=<-3,  6> velocity=< 2, -1>",
+        params: &[],
+        part_1: Some("#...#..###\n\
+                #...#...#.\n\
+                #...#...#.\n\
+                #####...#.\n\
+                #...#...#.\n\
+                #...#...#.\n\
+                #...#...#.\n\
+                #...#..###"),
+        part_2: Some("3"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle { stars: parse_lines(input, convert_line_to_variables)? })
+    }
+
+    fn part_1(&self) -> Answer {
+        match find_message(self.stars.clone()) {
+            // stars in pairs far apart line up too, but there's no reading that
+            Some((_seconds, sky)) if sky.bounds().is_some_and(|bounds| bounds.width() > MAX_MESSAGE_WIDTH || bounds.height() > MAX_MESSAGE_HEIGHT) => {
+                Answer::Unsolved(String::from("the points line up across too much sky to read"))
+            }
+            Some((_seconds, sky)) => Answer::Bitmap(render_sky(&sky)),
+            None => Answer::Unsolved(String::from("the points never spell a word")),
+        }
+    }
+
+    fn part_2(&self) -> Answer {
+        match find_message(self.stars.clone()) {
+            Some((seconds, _sky)) => Answer::from(seconds),
+            None => Answer::Unsolved(String::from("the points never spell a word")),
+        }
+    }
+
+    // the stars once they line up, blown up so the letters are readable
+    fn render(&self) -> Option<Image> {
+        let (_seconds, sky) = find_message(self.stars.clone())?;
+        let image = Image::from_fn(sky.bounds()?, |point| if sky.contains(point) { Rgb::WHITE } else { Rgb::BLACK });
+
+        return Some(image.scale(PIXELS_PER_STAR));
+    }
+
+    fn simulate(&self) -> Option<Box<dyn Simulation + '_>> {
+        return Some(Box::new(Sky { stars: self.stars.clone(), seconds: 0 }));
+    }
+
+    // the stars carry their own velocities, so the input isn't needed at all
+    fn resume(&self, snapshot: &Snapshot) -> Result<Box<dyn Simulation + '_>, String> {
+        let stars = snapshot.get("stars")?.split_whitespace().map(|star| {
+            match star.split(',').map(|value| value.parse()).collect::<Result<Vec<i64>, _>>().as_deref() {
+                Ok(&[x, y, dx, dy]) => Ok(Star { position: Point::new(x, y), velocity: Point::new(dx, dy) }),
+                _ => Err(format!("the snapshot's stars should look like x,y,dx,dy, not {}", star)),
+            }
+        });
+
+        return Ok(Box::new(Sky { stars: stars.collect::<Result<_, _>>()?, seconds: snapshot.tick as usize }));
+    }
+}
+
+// the stars a second at a time, for `aoc step`
+struct Sky {
+    stars: Vec<Star>,
+    seconds: usize,
+}
+
+impl Simulation for Sky {
+    fn tick(&self) -> u64 {
+        return self.seconds as u64;
+    }
+
+    fn step(&mut self) -> bool {
+        if self.seconds >= MAX_SECONDS {
+            return false;
+        }
+
+        move_stars(&mut self.stars);
+        self.seconds += 1;
+        return true;
+    }
+
+    // most of the time the stars are much too spread out to draw
+    fn view(&self, width: usize) -> Vec<String> {
+        let sky: SparseGrid<()> = self.stars.iter().map(|star| (star.position, ())).collect();
+
+        match sky.bounds() {
+            Some(bounds) if bounds.width() <= width && bounds.height() <= MAX_VIEW_ROWS => render_sky(&sky),
+            Some(bounds) => vec![format!("the stars are spread over {} by {}, too far apart to draw", bounds.width(), bounds.height())],
+            None => vec![String::from("no stars")],
+        }
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, i64)> {
+        return match Bounds::of(self.stars.iter().map(|star| star.position)) {
+            Some(bounds) => vec![("width", bounds.width() as i64), ("height", bounds.height() as i64)],
+            None => vec![("width", 0), ("height", 0)],
+        };
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let stars = self.stars.iter().map(|star| format!("{},{},{},{}", star.position.x, star.position.y, star.velocity.x, star.velocity.y));
+        return Snapshot::new(10, self.seconds as u64).with_list("stars", stars);
+    }
+}
+
+/// Moves every star on by one second.
+pub fn move_stars(stars: &mut [Star]) {
+    for star in stars.iter_mut() {
+        star.position = star.position + star.velocity;
+    }
+}
+
+/// How many seconds until the stars spell something, and the sky at that
+/// moment. None when they still haven't after a few hours.
+pub fn find_message(mut stars: Vec<Star>) -> Option<(usize, SparseGrid<()>)> {
+    let mut seconds = 0;
+    while seconds < MAX_SECONDS {
+        if cancel::requested() {
+            return None;
+        }
+
+        move_stars(&mut stars);
+        seconds += 1;
+
+        // every star moves every second, so they're all listed as changed
+        trace::emit(seconds as u64, || {
+            let summary = match Bounds::of(stars.iter().map(|star| star.position)) {
+                Some(bounds) => format!("sky {} by {}", bounds.width(), bounds.height()),
+                None => String::from("no stars"),
+            };
+            Event::new(summary).changed(stars.iter().map(|star| star.position))
+        });
+
+        // most seconds the first star is on its own, which is much cheaper to rule out than mapping the sky
+        let alone = |first: &Star| !stars.iter().any(|star| first.position.surrounding().contains(&star.position));
+        if stars.first().is_some_and(alone) {
+            continue;
+        }
+
+        let sky: SparseGrid<()> = stars.iter().map(|star| (star.position, ())).collect();
+        if points_spell_word(&sky) {
+            return Some((seconds, sky));
+        }
+    }
+
+    return None;
+}
+
+/// The sky as rows of `#` for a star and `.` for none.
+pub fn render_sky(sky: &SparseGrid<()>) -> Vec<String> {
+    return sky.render(|star| if star.is_some() { '#' } else { '.' });
+}
+
+/// Whether no star is on its own, which is the case once they spell
+/// something since every stroke of every letter is at least two stars long.
+pub fn points_spell_word(sky: &SparseGrid<()>) -> bool {
+    return sky.iter().all(|(point, _star)| sky.neighbours(point).next().is_some());
+}
+
+/// One star, from a line like `position=< 9,  1> velocity=< 0,  2>`.
+pub fn convert_line_to_variables(str: &str) -> Result<Star, ParseError> {
+    return parse_line(str, star);
+}
+
+// position=< 9,  1> velocity=< 0,  2>, padded with however many spaces
+fn star(input: &mut Input) -> Parsed<Star> {
+    literal("position=<").parse(input)?;
+    let position = pair(input)?;
+    literal(">").parse(input)?;
+    whitespace().parse(input)?;
+    literal("velocity=<").parse(input)?;
+    let velocity = pair(input)?;
+    literal(">").parse(input)?;
+
+    return Ok(Star { position, velocity });
+}
+
+fn pair(input: &mut Input) -> Parsed<Point> {
+    let x = padded(signed()).parse(input)?;
+    literal(",").parse(input)?;
+    let y = padded(signed()).parse(input)?;
+    whitespace().parse(input)?;
+
+    return Ok(Point::new(x, y));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn no_message_is_unsolved() {
+        // the stars only ever drift apart
+        let puzzle = Puzzle::parse("position=< 0, 0> velocity=< 1, 0>\nposition=< 5, 0> velocity=< 2, 0>").unwrap();
+
+        assert_eq!(puzzle.part_1(), Answer::Unsolved(String::from("the points never spell a word")));
+        assert_eq!(puzzle.part_2(), Answer::Unsolved(String::from("the points never spell a word")));
+    }
+
+    #[test]
+    fn spread_out_message_is_unsolved() {
+        let puzzle = Puzzle::parse("position=< 0, 0> velocity=< 0, 0>\nposition=< 1, 0> velocity=< 0, 0>\n\
+                                    position=< 1000000, 1000000> velocity=< 0, 0>\nposition=< 1000001, 1000000> velocity=< 0, 0>").unwrap();
+
+        assert_eq!(puzzle.part_1(), Answer::Unsolved(String::from("the points line up across too much sky to read")));
+        assert_eq!(puzzle.part_2(), Answer::from(1));
+    }
+
+    #[test]
+    fn render_works() {
+        let image = Puzzle::parse(EXAMPLES[0].input).unwrap().render().unwrap();
+
+        assert_eq!((image.width(), image.height()), (10 * PIXELS_PER_STAR, 8 * PIXELS_PER_STAR));
+        assert_eq!(image.pixel(0, 0), Rgb::WHITE);
+        assert_eq!(image.pixel(PIXELS_PER_STAR, 0), Rgb::BLACK);
+    }
+
+    #[test]
+    fn simulate_works() {
+        let puzzle = Puzzle::parse(EXAMPLES[0].input).unwrap();
+        let mut sky = puzzle.simulate().unwrap();
+
+        assert_eq!(sky.view(80).len(), 16);
+        for _ in 0..3 {
+            sky.step();
+        }
+
+        assert_eq!(sky.metrics(), vec![("width", 10), ("height", 8)]);
+        assert_eq!(sky.view(80)[0], "#...#..###");
+        assert_eq!(sky.view(5).len(), 1);
+
+        let snapshot = sky.snapshot();
+        assert!(snapshot.get("stars").unwrap().starts_with("9,7,0,2 4,0,-1,0 "));
+
+        let resumed = puzzle.resume(&snapshot).unwrap();
+        assert_eq!((resumed.tick(), resumed.view(80)), (3, sky.view(80)));
+
+        let broken = Snapshot::new(10, 3).with("stars", "3,7,0");
+        assert_eq!(puzzle.resume(&broken).err(), Some(String::from("the snapshot's stars should look like x,y,dx,dy, not 3,7,0")));
+    }
+
+    #[test]
+    fn convert_line_to_variables_works() {
+        assert_eq!(
+            convert_line_to_variables("position=<-41214,-10223>velocity=<4,1>"),
+            Ok(Star { position: Point::new(-41214, -10223), velocity: Point::new(4, 1) })
+        );
+
+        assert_eq!(
+            convert_line_to_variables("position=< 10585,  41558> velocity=<-1, -4>"),
+            Ok(Star { position: Point::new(10585, 41558), velocity: Point::new(-1, -4) })
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given up to 100 points.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 100 };
+
+// no generated message takes longer than this to appear
+const MAX_SECONDS: i64 = 10400;
+
+// (position x, position y, velocity x, velocity y)
+fn points(input: &str) -> Vec<(i64, i64, i64, i64)> {
+    return input
+        .lines()
+        .map(|line| {
+            let numbers: Vec<i64> = line
+                .split(|c: char| c != '-' && !c.is_ascii_digit())
+                .filter(|number| !number.is_empty())
+                .map(|number| number.parse().unwrap())
+                .collect();
+
+            (numbers[0], numbers[1], numbers[2], numbers[3])
+        })
+        .collect();
+}
+
+fn positions(points: &[(i64, i64, i64, i64)], seconds: i64) -> Vec<(i64, i64)> {
+    return points.iter().map(|p| (p.0 + p.2 * seconds, p.1 + p.3 * seconds)).collect();
+}
+
+// (min x, min y, max x, max y)
+fn bounds(positions: &[(i64, i64)]) -> (i64, i64, i64, i64) {
+    let xs = positions.iter().map(|p| p.0);
+    let ys = positions.iter().map(|p| p.1);
+
+    return (xs.clone().min().unwrap(), ys.clone().min().unwrap(), xs.max().unwrap(), ys.max().unwrap());
+}
+
+// the message is showing when the points are packed into the smallest box
+fn message_seconds(points: &[(i64, i64, i64, i64)]) -> i64 {
+    let area = |seconds| {
+        let (min_x, min_y, max_x, max_y) = bounds(&positions(points, seconds));
+        (max_x - min_x + 1) * (max_y - min_y + 1)
+    };
+
+    return (0..=MAX_SECONDS).min_by_key(|&seconds| area(seconds)).unwrap();
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let points = points(input);
+    let positions = positions(&points, message_seconds(&points));
+    let (min_x, min_y, max_x, max_y) = bounds(&positions);
+
+    let rows = (min_y..=max_y)
+        .map(|y| (min_x..=max_x).map(|x| if positions.contains(&(x, y)) { '#' } else { '.' }).collect())
+        .collect();
+
+    return Answer::Bitmap(rows);
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    return Answer::from(message_seconds(&points(input)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
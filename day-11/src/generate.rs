@@ -0,0 +1,26 @@
+use common::rng::Rng;
+
+/// The largest serial number `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 10_000;
+
+/// The input is just a grid serial number, `size` is the largest one to pick.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    return rng.range(1, size.max(1) as i64).to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..3 {
+            let input = generate(&mut Rng::new(seed), DEFAULT_SIZE);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert!(input.parse::<i64>().unwrap() <= DEFAULT_SIZE as i64);
+            puzzle.part_1();
+        }
+    }
+}
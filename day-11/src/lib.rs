@@ -0,0 +1,249 @@
+//! Day 11: Chronal Charge. Every fuel cell in a 300 by 300 grid has a power
+//! level worked out from its position and the grid's serial number. Part 1 is
+//! the 3 by 3 square with the most power, part 2 the square of any size.
+//!
+//! ```
+//! use grid::Point;
+//!
+//! let grid = day_11::create_grid(18, 300);
+//! assert_eq!(day_11::find_largest_sub_grid(3, &grid), (Point::new(33, 45), 29));
+//! assert_eq!(day_11::power_of_sub_grid(Point::new(33, 45), 3, &grid), 29);
+//! ```
+
+#![warn(missing_docs)]
+
+use common::parser::{parse_line, unsigned};
+use common::{Answer, Example, Params, ParseError, Solution};
+use grid::image::ColourMap;
+use grid::{Bounds, DenseGrid, Image, Point};
+
+/// A random grid serial number.
+pub mod generate;
+/// Adds up every square of every size cell by cell.
+pub mod reference;
+
+const GRID_SIZE: usize = 300;
+
+/// The serial number and how big a grid it makes.
+pub struct Puzzle {
+    serial: i32,
+    grid_size: usize,
+}
+
+/// The puzzle text's grids with serial numbers 18 and 42.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "serial 18",
+        input: "18",
+        params: &[],
+        part_1: Some("33,45"),
+        part_2: Some("90,269,16"),
+    },
+    Example {
+        name: "serial 42",
+        input: "42",
+        params: &[],
+        part_1: Some("21,61"),
+        part_2: Some("232,251,12"),
+    },
+];
+
+impl Solution for Puzzle {
+    // the whole input is the grid serial number, which is never negative
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let serial = parse_line(input.trim(), unsigned())?;
+
+        Ok(Puzzle { serial, grid_size: GRID_SIZE })
+    }
+
+    // a smaller grid keeps the brute force reference solver quick
+    fn configure(&mut self, params: &Params) -> Result<(), String> {
+        self.serial = params.get("serial", self.serial)?;
+        self.grid_size = params.get("grid_size", self.grid_size)?;
+
+        if self.grid_size < 3 {
+            return Err(String::from("grid_size must be at least 3"));
+        }
+
+        return Ok(());
+    }
+
+    // part 2 is cubic in the grid size, so nothing bigger than the puzzle's own
+    fn check_size(&self) -> Result<(), String> {
+        if self.grid_size > GRID_SIZE {
+            return Err(format!("grid_size {} is more than the {} allowed", self.grid_size, GRID_SIZE));
+        }
+
+        return Ok(());
+    }
+
+    // the grid is only made once a part asks for it, so it counts towards that part's time
+    fn part_1(&self) -> Answer {
+        let (top_left, _power) = find_largest_sub_grid(3, &create_grid(self.serial, self.grid_size));
+
+        return Answer::Coordinate(vec![top_left.x, top_left.y]);
+    }
+
+    fn part_2(&self) -> Answer {
+        let (top_left, size, _power) = find_largest_square(&create_grid(self.serial, self.grid_size));
+
+        return Answer::Coordinate(vec![top_left.x, top_left.y, size as i64]);
+    }
+
+    // every cell's power level, from -5 in black up to 4 in white
+    fn render(&self) -> Option<Image> {
+        return Some(Image::from_grid(&create_grid(self.serial, self.grid_size), |&power| ColourMap::Heat.between(f64::from(power), -5.0, 4.0)));
+    }
+}
+
+/// Every fuel cell's power level, numbered from 1 in both directions.
+pub fn create_grid(serial_number: i32, grid_size: usize) -> DenseGrid<i32> {
+    let bounds = Bounds::new(Point::new(1, 1), Point::new(grid_size as i64, grid_size as i64));
+
+    // in i64 so a big serial number can't overflow
+    return DenseGrid::from_fn(bounds, |cell| {
+        let rack_id = cell.x + 10;
+        let starting_power_level = rack_id * cell.y;
+        let increased_power_level = starting_power_level + i64::from(serial_number);
+        let power_level = increased_power_level * rack_id;
+
+        // 0 when there isn't one
+        let hundreds_digit = (power_level / 100).abs() % 10;
+
+        return hundreds_digit as i32 - 5;
+    });
+}
+
+// the top left corners a k by k square fits at, a column at a time so ties go to the smallest x
+fn corners(k: usize, grid: &DenseGrid<i32>) -> impl Iterator<Item = Point> {
+    let Bounds { min, max } = grid.bounds();
+    let last = max - Point::new(k as i64 - 1, k as i64 - 1);
+
+    return (min.x..=last.x).flat_map(move |x| (min.y..=last.y).map(move |y| Point::new(x, y)));
+}
+
+/// The top left corner of the `k` by `k` square with the most power, and
+/// its power. Ties go to the smallest x, then the smallest y.
+pub fn find_largest_sub_grid(k: usize, grid: &DenseGrid<i32>) -> (Point, i32) {
+    let mut highest_power = -100000;
+    let mut highest_coordinate = Point::default();
+
+    for top_left in corners(k, grid) {
+        let power_total = power_of_sub_grid(top_left, k, grid);
+        if power_total > highest_power {
+            highest_power = power_total;
+            highest_coordinate = top_left;
+        }
+    }
+
+    return (highest_coordinate, highest_power);
+}
+
+/// The top left corner, size and power of the square of any size with the
+/// most power. Checking every square size with power_of_sub_grid is far too
+/// slow, so this builds a summed-area table where sums\[x\]\[y\] is the total
+/// of every cell above and to the left of (x, y). The power of any square is
+/// then 4 lookups.
+pub fn find_largest_square(grid: &DenseGrid<i32>) -> (Point, usize, i32) {
+    let bounds = grid.bounds();
+    let size = bounds.width();
+    let mut sums = vec![vec![0_i32; size + 1]; size + 1];
+
+    for (y, row) in (bounds.min.y..=bounds.max.y).map(|y| grid.row(y).unwrap()).enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            sums[x + 1][y + 1] = cell + sums[x][y + 1] + sums[x + 1][y] - sums[x][y];
+        }
+    }
+
+    let mut highest_power = i32::MIN;
+    let mut highest_coordinate = Point::default();
+    let mut highest_size = 0;
+
+    for k in 1..=size {
+        for x in 0..=(size - k) {
+            for y in 0..=(size - k) {
+                let power_total = sums[x + k][y + k] - sums[x][y + k] - sums[x + k][y] + sums[x][y];
+
+                if power_total > highest_power {
+                    highest_power = power_total;
+                    highest_coordinate = bounds.min + Point::new(x as i64, y as i64);
+                    highest_size = k;
+                }
+            }
+        }
+    }
+
+    return (highest_coordinate, highest_size, highest_power);
+}
+
+/// The total power of the square `sub_grid_size` cells across from `top_left`.
+pub fn power_of_sub_grid(top_left: Point, sub_grid_size: usize, grid: &DenseGrid<i32>) -> i32 {
+    let mut power_total = 0;
+    let column = (top_left.x - grid.bounds().min.x) as usize;
+
+    for y in top_left.y..top_left.y + sub_grid_size as i64 {
+        if let Some(row) = grid.row(y) {
+            power_total += row.iter().skip(column).take(sub_grid_size).sum::<i32>();
+        }
+    }
+
+    return power_total;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn render_works() {
+        let image = Puzzle::parse("18").unwrap().render().unwrap();
+
+        assert_eq!((image.width(), image.height()), (GRID_SIZE, GRID_SIZE));
+        assert_eq!(image.pixel(32, 44), ColourMap::Heat.between(4.0, -5.0, 4.0));
+    }
+
+    #[test]
+    fn create_grid_works() {
+        // the puzzle text's fuel cells
+        for &(x, y, serial, power) in [(3, 5, 8, 4), (122, 79, 57, -5), (217, 196, 39, 0), (101, 153, 71, 4)].iter() {
+            assert_eq!(create_grid(serial, GRID_SIZE).get(Point::new(x, y)), Some(&power));
+        }
+
+        // a negative serial number from --set still has a hundreds digit, -10879's is 8
+        assert_eq!(create_grid(-1000, 3).get(Point::new(1, 1)), Some(&3));
+        assert!(create_grid(i32::MAX, 3).get(Point::new(3, 3)).is_some());
+    }
+
+    #[test]
+    fn check_size_works() {
+        let mut puzzle = Puzzle::parse("18").unwrap();
+        assert_eq!(puzzle.check_size(), Ok(()));
+
+        common::configure(&mut puzzle, &Params::from_pairs(&[("grid_size", "100000")])).unwrap();
+        assert!(puzzle.check_size().is_err());
+        assert!(common::configure(&mut puzzle, &Params::from_pairs(&[("grid_size", "2")])).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Puzzle::parse("-100").err().unwrap().column, 1);
+        assert_eq!(Puzzle::parse("18x").err().unwrap().column, 3);
+    }
+
+    #[test]
+    fn find_largest_sub_grid_works() {
+        let grid = create_grid(18, GRID_SIZE);
+        assert_eq!(find_largest_sub_grid(3, &grid), (Point::new(33, 45), 29));
+    }
+
+    #[test]
+    fn find_largest_square_works() {
+        let grid = create_grid(18, GRID_SIZE);
+        assert_eq!(find_largest_square(&grid), (Point::new(90, 269), 16, 113));
+    }
+}
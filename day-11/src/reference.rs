@@ -0,0 +1,75 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Uses a 30 by 30 grid, since every square of every size is summed cell by cell.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[("grid_size", "30")], max_size: 10_000 };
+
+fn power_level(x: i64, y: i64, serial: i64) -> i64 {
+    let rack_id = x + 10;
+    return ((rack_id * y + serial) * rack_id / 100) % 10 - 5;
+}
+
+fn square_power(left: i64, top: i64, size: i64, serial: i64) -> i64 {
+    let mut power = 0;
+
+    for x in left..left + size {
+        for y in top..top + size {
+            power += power_level(x, y, serial);
+        }
+    }
+
+    return power;
+}
+
+// (x, y, size) of the first most powerful square, going by size then x then y
+fn most_powerful(serial: i64, grid_size: i64, sizes: &[i64]) -> Vec<i64> {
+    let mut best = (i64::MIN, vec![]);
+
+    for &size in sizes {
+        for x in 1..=grid_size - size + 1 {
+            for y in 1..=grid_size - size + 1 {
+                let power = square_power(x, y, size, serial);
+
+                if power > best.0 {
+                    best = (power, vec![x, y, size]);
+                }
+            }
+        }
+    }
+
+    return best.1;
+}
+
+fn part_1(input: &str, params: &Params) -> Answer {
+    let serial = input.trim().parse().unwrap();
+    let grid_size = params.get("grid_size", 300).unwrap();
+
+    return Answer::Coordinate(most_powerful(serial, grid_size, &[3])[..2].to_vec());
+}
+
+fn part_2(input: &str, params: &Params) -> Answer {
+    let serial = input.trim().parse().unwrap();
+    let grid_size = params.get("grid_size", 300).unwrap();
+    let sizes: Vec<i64> = (1..=grid_size).collect();
+
+    return Answer::Coordinate(most_powerful(serial, grid_size, &sizes));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+    use common::Example;
+
+    #[test]
+    fn reference_solves_examples() {
+        // every square size on the full grid would take hours, so only part 1 is checked
+        let examples: Vec<Example> = crate::EXAMPLES.iter().map(|example| Example { part_2: None, ..*example }).collect();
+        differential::assert_reference_examples(&REFERENCE, &examples);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
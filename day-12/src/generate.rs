@@ -0,0 +1,40 @@
+use common::rng::Rng;
+
+/// How many pots wide an initial state `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// An initial state `size` pots wide followed by a note for every one of the
+/// 32 patterns. An empty neighbourhood always stays empty, otherwise plants
+/// would appear out of nowhere along the infinite row.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pot = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
+
+    let initial_state: String = (0..size.max(1)).map(|_| pot(rng)).collect();
+    let mut lines = vec![format!("initial state: {}", initial_state), String::new()];
+
+    for pattern in 0..32 {
+        let pattern: String = (0..5).rev().map(|bit| if pattern & (1 << bit) != 0 { '#' } else { '.' }).collect();
+        let next = if pattern == "....." { '.' } else { pot(rng) };
+
+        lines.push(format!("{} => {}", pattern, next));
+    }
+
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 40);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.lines().count(), 34);
+            puzzle.part_1();
+        }
+    }
+}
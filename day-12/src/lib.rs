@@ -0,0 +1,601 @@
+//! Day 12: Subterranean Sustainability. A row of pots, some with plants,
+//! changes every generation following notes about each pot and its two
+//! neighbours either side. Both parts want the sum of the numbers of the pots
+//! with plants, part 2 after fifty billion generations.
+//!
+//! ```
+//! let notes = vec![day_12::create_note("..#.. => #").unwrap(), day_12::create_note(".#... => #").unwrap()];
+//! let mut plants = day_12::load_plants_from_str("#...#", 0);
+//!
+//! assert_eq!(day_12::next_generation(&notes, &mut plants), vec![1, 5]);
+//! assert_eq!(day_12::calculate_count(&plants), 10);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::collections::VecDeque;
+use std::collections::HashMap;
+
+use common::cancel;
+use common::parser::{literal, many, one_of, parse_line, repeat, Input, Parsed, Parser};
+use common::snapshot::Snapshot;
+use common::stepper::{self, Simulation};
+use common::trace::{self, Event};
+use common::{parse_lines, Answer, Example, Params, ParseError, Solution};
+
+/// A row of pots and a note for each of the 32 patterns.
+pub mod generate;
+/// Simulates every generation, with no shortcut for part 2's fifty billion.
+pub mod reference;
+
+/// Whether a pot has a plant in it.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum PlantState {
+    /// No plant, `.`
+    Empty,
+    /// A plant, `#`
+    Filled
+}
+
+/// One pot of the row.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Plant {
+    /// The pot's number, pots left of the first one in the input are negative.
+    pub position: i64,
+    /// Whether the pot has a plant.
+    pub state: PlantState
+}
+
+/// A note like `..#.# => #`: a pot with these neighbours has or doesn't have a
+/// plant next generation.
+#[derive(Debug, PartialEq)]
+pub struct Note {
+    /// What the pot becomes.
+    pub next_state: PlantState,
+    /// The pot itself.
+    pub current: PlantState,
+    /// The pot two to the left.
+    pub left_left_side: PlantState,
+    /// The pot one to the left.
+    pub left_side: PlantState,
+    /// The pot two to the right.
+    pub right_right_side: PlantState,
+    /// The pot one to the right.
+    pub right_side: PlantState
+}
+
+struct Change {
+    index: usize,
+    next_state: PlantState
+}
+
+const INITIAL_STATE: &str = "#...#..###.#.###.####.####.#..#.##..#..##..#.....#.#.#.##.#...###.#..##..#.##..###..#..##.#..##...";
+
+// part 1 simulates every one of these
+const PART_1_GENERATIONS: i64 = 20;
+
+// part 2 looks this far ahead, too far to simulate every generation
+const GENERATIONS: i64 = 50_000_000_000;
+
+// the most generations the server will have either part go through, part 1 simulates every one
+// of its own but part 2 can skip ahead from the puzzle's fifty billion
+const MAX_PART_1_GENERATIONS: i64 = 100_000;
+const MAX_GENERATIONS: i64 = GENERATIONS;
+
+// how long the sum has to grow by the same amount before we assume it always will
+const STABLE_GENERATIONS: usize = 100;
+
+/// The pots to start with, the notes and how many generations each part wants.
+pub struct Puzzle {
+    initial_state: String,
+    notes: Vec<Note>,
+    part_1_generations: i64,
+    generations: i64,
+}
+
+/// The puzzle text's pots and notes, which only say which patterns grow a plant.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "pots",
+        input: "initial state: #..#.#..##......###...###\n\
+                \n\
+                ...## => #\n\
+                ..#.. => #\n\
+                .#... => #\n\
+                .#.#. => #\n\
+                .#.## => #\n\
+                .##.. => #\n\
+                .#### => #\n\
+                #.#.# => #\n\
+                #.### => #\n\
+                ##.#. => #\n\
+                ##.## => #\n\
+                ###.. => #\n\
+                ###.# => #\n\
+                ####. => #",
+        params: &[],
+        part_1: Some("325"),
+        part_2: None,
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let (initial_state, notes_start) = parse_initial_state(input)?;
+
+        // skip past the header so errors still point at the right line
+        let notes_input: Vec<&str> = input.lines().skip(notes_start).collect();
+        let notes = parse_lines(&notes_input.join("\n"), create_note).map_err(|e| {
+            let line = e.line + notes_start;
+            e.at_line(line)
+        })?;
+
+        Ok(Puzzle { initial_state, notes, part_1_generations: PART_1_GENERATIONS, generations: GENERATIONS })
+    }
+
+    fn configure(&mut self, params: &Params) -> Result<(), String> {
+        self.part_1_generations = params.get("part_1_generations", self.part_1_generations)?;
+        self.generations = params.get("generations", self.generations)?;
+
+        // the pots are checked the same way as the input's "initial state:" line
+        let initial_state: String = params.get("initial_state", self.initial_state.clone())?;
+        self.initial_state = parse_line(&initial_state, many(one_of(".#")))
+            .map_err(|e| format!("invalid initial_state, expected {} at column {}", e.expected, e.column))?
+            .into_iter()
+            .collect();
+
+        return Ok(());
+    }
+
+    fn check_size(&self) -> Result<(), String> {
+        if self.part_1_generations > MAX_PART_1_GENERATIONS {
+            return Err(format!("part_1_generations {} is more than the {} allowed", self.part_1_generations, MAX_PART_1_GENERATIONS));
+        }
+        if self.generations > MAX_GENERATIONS {
+            return Err(format!("generations {} is more than the {} allowed", self.generations, MAX_GENERATIONS));
+        }
+
+        return Ok(());
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut plants = load_plants_from_str(&self.initial_state, 0);
+
+        for i in 0..self.part_1_generations {
+            if cancel::requested() {
+                break;
+            }
+
+            let flipped = next_generation(&self.notes, &mut plants);
+            trace::emit(i as u64 + 1, || Event::new(format!("total {}", calculate_count(&plants))).changed(flipped));
+        }
+
+        return Answer::from(calculate_count(&plants));
+    }
+
+    fn part_2(&self) -> Answer {
+        let generations = self.generations;
+        let mut plants = load_plants_from_str(&self.initial_state, 0);
+
+        let mut previous_total = calculate_count(&plants);
+        let mut previous_diff = 0;
+        let mut stable_generations = 0;
+
+        for i in 0..generations {
+            // only a pattern that never settles gets anywhere near the full count
+            if cancel::requested() {
+                break;
+            }
+
+            let flipped = next_generation(&self.notes, &mut plants);
+
+            let total = calculate_count(&plants);
+            let diff = total - previous_total;
+            trace::emit(i as u64 + 1, || Event::new(format!("total {} diff {}", total, diff)).changed(flipped));
+
+            if diff == previous_diff {
+                stable_generations += 1;
+            } else {
+                stable_generations = 0;
+            }
+
+            // the plants have settled into a pattern that just shifts along the row
+            if stable_generations == STABLE_GENERATIONS {
+                let skipped = diff.checked_mul(generations - i - 1).and_then(|skipped| skipped.checked_add(total));
+                return match skipped {
+                    Some(total) => Answer::from(total),
+                    None => Answer::Unsolved(format!("the total after {} generations is too big to count", generations)),
+                };
+            }
+
+            previous_total = total;
+            previous_diff = diff;
+        }
+
+        return Answer::from(previous_total);
+    }
+
+    fn simulate(&self) -> Option<Box<dyn Simulation + '_>> {
+        let plants = load_plants_from_str(&self.initial_state, 0);
+        let total = calculate_count(&plants);
+
+        return Some(Box::new(Pots {
+            notes: &self.notes,
+            plants,
+            generation: 0,
+            generations: self.generations,
+            total,
+            diff: 0,
+            flipped: vec![],
+        }));
+    }
+
+    fn resume(&self, snapshot: &Snapshot) -> Result<Box<dyn Simulation + '_>, String> {
+        let first: i64 = snapshot.get_parsed("first")?;
+        let pots = snapshot.get("pots")?;
+
+        if let Err(e) = parse_line(pots, many(one_of(".#"))) {
+            return Err(format!("the snapshot's pots should be . or #, not what's at column {}", e.column));
+        }
+
+        let plants: VecDeque<Plant> = pots.chars().enumerate()
+            .map(|(i, pot)| Plant { position: first + i as i64, state: char_to_state(pot) })
+            .collect();
+
+        return Ok(Box::new(Pots {
+            notes: &self.notes,
+            total: calculate_count(&plants),
+            plants,
+            generation: snapshot.tick as i64,
+            generations: self.generations,
+            diff: snapshot.get_parsed("diff")?,
+            flipped: vec![],
+        }));
+    }
+}
+
+// the row of pots one generation at a time, for `aoc step`
+struct Pots<'a> {
+    notes: &'a [Note],
+    plants: VecDeque<Plant>,
+    generation: i64,
+    generations: i64,
+    total: i64,
+    diff: i64,
+    flipped: Vec<i64>,
+}
+
+impl Simulation for Pots<'_> {
+    fn tick(&self) -> u64 {
+        return self.generation as u64;
+    }
+
+    fn step(&mut self) -> bool {
+        if self.generation >= self.generations {
+            return false;
+        }
+
+        self.flipped = next_generation(self.notes, &mut self.plants);
+        self.generation += 1;
+
+        let total = calculate_count(&self.plants);
+        self.diff = total - self.total;
+        self.total = total;
+
+        return true;
+    }
+
+    // the pots from the first plant on, with the ones that just changed highlighted
+    fn view(&self, width: usize) -> Vec<String> {
+        let first = match self.plants.iter().find(|plant| plant.state == PlantState::Filled) {
+            Some(plant) => plant.position,
+            None => return vec![String::from("no plants left")],
+        };
+
+        let row: String = self.plants.iter()
+            .filter(|plant| plant.position >= first)
+            .take(width)
+            .map(|plant| {
+                let pot = if plant.state == PlantState::Filled { "#" } else { "." };
+                if self.flipped.contains(&plant.position) { stepper::highlight(pot) } else { pot.to_string() }
+            })
+            .collect();
+
+        return vec![format!("pots from {}:", first), row];
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, i64)> {
+        let plants = self.plants.iter().filter(|plant| plant.state == PlantState::Filled).count();
+        return vec![("total", self.total), ("diff", self.diff), ("plants", plants as i64)];
+    }
+
+    // the pots from the first plant to the last, next_generation adds back the empty ones around them
+    fn snapshot(&self) -> Snapshot {
+        let filled = |plant: &&Plant| plant.state == PlantState::Filled;
+        let first = self.plants.iter().find(filled).map_or(0, |plant| plant.position);
+        let last = self.plants.iter().rev().find(filled).map_or(-1, |plant| plant.position);
+
+        let pots: String = self.plants.iter()
+            .filter(|plant| plant.position >= first && plant.position <= last)
+            .map(|plant| if plant.state == PlantState::Filled { '#' } else { '.' })
+            .collect();
+
+        return Snapshot::new(12, self.generation as u64)
+            .with("first", first)
+            .with("diff", self.diff)
+            .with("pots", pots);
+    }
+}
+
+/*
+ * The puzzle input starts with "initial state: #..#.#" and a blank line before
+ * the notes. Inputs holding only the notes fall back to INITIAL_STATE. Returns
+ * the state and how many lines it took up.
+ */
+fn parse_initial_state(input: &str) -> Result<(String, usize), ParseError> {
+    let first_line = input.lines().next().unwrap_or("");
+
+    if !first_line.starts_with("initial") {
+        return Ok((INITIAL_STATE.to_string(), 0));
+    }
+
+    let state = parse_line(first_line, initial_state)?;
+
+    let blank_lines = input.lines().skip(1).take_while(|line| line.trim().is_empty()).count();
+
+    return Ok((state.to_string(), 1 + blank_lines));
+}
+
+fn char_to_state(plant_state: char) -> PlantState {
+    match plant_state {
+        '.' => return PlantState::Empty,
+        _ => return PlantState::Filled
+    }
+}
+
+fn initial_state(input: &mut Input) -> Parsed<String> {
+    literal("initial state: ").parse(input)?;
+    let pots = many(one_of(".#")).parse(input)?;
+
+    return Ok(pots.into_iter().collect());
+}
+
+/// One note, like `...## => #`.
+pub fn create_note(note: &str) -> Result<Note, ParseError> {
+    return parse_line(note, note_parser);
+}
+
+// ...## => #
+fn note_parser(input: &mut Input) -> Parsed<Note> {
+    let pots = repeat(5, one_of(".#")).parse(input)?;
+    literal(" => ").parse(input)?;
+    let next_state = one_of(".#").parse(input)?;
+
+    return Ok(Note {
+        current: char_to_state(pots[2]),
+        next_state: char_to_state(next_state),
+        left_left_side: char_to_state(pots[0]),
+        left_side: char_to_state(pots[1]),
+        right_side: char_to_state(pots[3]),
+        right_right_side: char_to_state(pots[4]),
+    });
+}
+
+fn create_boundry_plant(position: i64) -> Plant {
+    return Plant {
+        position,
+        state: PlantState::Empty
+    };
+}
+
+/// Moves the row on a generation, adding empty pots at either end when
+/// plants could spread into them. Returns the positions of the pots that
+/// gained or lost a plant, in order. A pattern without a note leaves the pot
+/// empty.
+pub fn next_generation(notes: &[Note], current_gen: &mut VecDeque<Plant>) -> Vec<i64> {
+    // a plant can spread two pots past either end, so those pots have to exist first
+    while current_gen.iter().take(2).any(|plant| plant.state == PlantState::Filled) {
+        current_gen.push_front(create_boundry_plant(current_gen.front().unwrap().position - 1));
+    }
+    while current_gen.iter().rev().take(2).any(|plant| plant.state == PlantState::Filled) {
+        current_gen.push_back(create_boundry_plant(current_gen.back().unwrap().position + 1));
+    }
+
+    let mut changes: HashMap<usize, Change> = HashMap::new();
+
+    for (i, plant) in current_gen.iter().enumerate() {
+        let ll_side = if i < 2 { PlantState::Empty } else { current_gen.get(i - 2).unwrap().state };
+        let l_side = if i < 1 { PlantState::Empty } else { current_gen.get(i - 1).unwrap().state };
+        let r_side = if i > current_gen.len() - 2 { PlantState::Empty } else { current_gen.get(i + 1).unwrap().state };
+        let rr_side = if i > current_gen.len() - 3 { PlantState::Empty } else { current_gen.get(i + 2).unwrap().state };
+
+        // a pattern without a note leaves the pot empty, the example only lists the ones that grow
+        changes.insert(i, Change { index: i, next_state: PlantState::Empty });
+
+        for note in notes.iter() {
+            if plant.state == note.current &&
+                ll_side == note.left_left_side &&
+                l_side == note.left_side &&
+                r_side == note.right_side &&
+                rr_side == note.right_right_side {
+                changes.insert(i, Change { index: i, next_state: note.next_state });
+            }
+        }
+    }
+
+    let mut flipped = vec![];
+
+    for (_i, change) in changes.iter() {
+        let plant = current_gen.get_mut(change.index).unwrap();
+
+        if plant.state != change.next_state {
+            flipped.push(plant.position);
+        }
+        plant.state = change.next_state;
+    }
+
+    flipped.sort();
+    return flipped;
+}
+
+/// The row from pots written like `#..#.`, numbered from `start_idx`, with
+/// two empty pots either side.
+pub fn load_plants_from_str(initial_state: &str, start_idx: i64) -> VecDeque<Plant> {
+    let mut plants: VecDeque<Plant> = VecDeque::new();
+
+    // because we have to check 2 plants on each side we need to add empty plants
+    let buffer_plants = 2;
+
+    for i in 0..buffer_plants {
+        plants.push_back(Plant { position: i - buffer_plants + start_idx, state: PlantState::Empty });
+    }
+
+    for (i, char) in initial_state.chars().enumerate() {
+        let index = i as i64 + start_idx;
+        match char {
+            '.' => plants.push_back(Plant { position: index, state: PlantState::Empty }),
+            '#' => plants.push_back(Plant { position: index, state: PlantState::Filled }),
+            _ => {}
+        }
+    }
+
+    for i in 0..buffer_plants {
+        plants.push_back(Plant { position: i + initial_state.len() as i64 + start_idx, state: PlantState::Empty });
+    }
+
+    return plants;
+}
+
+/// The numbers of every pot with a plant, added up.
+pub fn calculate_count(plants: &VecDeque<Plant>) -> i64 {
+    let mut running_count = 0;
+
+    for plant in plants.iter() {
+        if plant.state == PlantState::Filled {
+            running_count += plant.position;
+        }
+    }
+
+    return running_count;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn check_size_works() {
+        let mut puzzle = Puzzle::parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(puzzle.check_size(), Ok(()));
+
+        common::configure(&mut puzzle, &Params::from_pairs(&[("part_1_generations", "1000000")])).unwrap();
+        assert!(puzzle.check_size().is_err());
+
+        // the pattern settles soon enough, but skipping this far ahead overflows
+        let mut puzzle = Puzzle::parse(EXAMPLES[0].input).unwrap();
+        common::configure(&mut puzzle, &Params::from_pairs(&[("generations", &i64::MAX.to_string())])).unwrap();
+        assert!(puzzle.check_size().is_err());
+        assert!(matches!(puzzle.part_2(), Answer::Unsolved(_)));
+    }
+
+    #[test]
+    fn load_plants_from_str_works() {
+        assert_eq!(load_plants_from_str(".#.", 0), VecDeque::from(vec![
+            Plant { position: -2, state: PlantState::Empty },
+            Plant { position: -1, state: PlantState::Empty },
+            Plant { position: 0, state: PlantState::Empty },
+            Plant { position: 1, state: PlantState::Filled },
+            Plant { position: 2, state: PlantState::Empty },
+            Plant { position: 3, state: PlantState::Empty },
+            Plant { position: 4, state: PlantState::Empty }
+        ]));
+    }
+
+    #[test]
+    fn calculate_count_works() {
+        let plants = load_plants_from_str("...##....#####...#######....#.#..##.", 0);
+        assert_eq!(calculate_count(&plants), 327);
+
+        let plants = load_plants_from_str(".", 0);
+        assert_eq!(calculate_count(&plants), 0);
+
+        let plants = load_plants_from_str("##", -1);
+        assert_eq!(calculate_count(&plants), -1);
+    }
+
+    #[test]
+    fn create_note_works() {
+        assert_eq!(create_note("...#. => #"), Ok(Note {
+            current: PlantState::Empty,
+            next_state: PlantState::Filled,
+            left_left_side: PlantState::Empty,
+            left_side: PlantState::Empty,
+            right_side: PlantState::Filled,
+            right_right_side: PlantState::Empty
+        }));
+    }
+
+    #[test]
+    fn create_note_errors() {
+        assert_eq!(create_note("..x#. => #").unwrap_err().column, 3);
+        assert_eq!(create_note("...#. -> #").unwrap_err().column, 6);
+        assert_eq!(create_note("...#. => ?").unwrap_err().column, 10);
+    }
+
+    #[test]
+    fn parse_initial_state_works() {
+        assert_eq!(parse_initial_state("initial state: #..#\n\n...## => #"), Ok((String::from("#..#"), 2)));
+        assert_eq!(parse_initial_state("...## => #"), Ok((INITIAL_STATE.to_string(), 0)));
+        assert_eq!(parse_initial_state("initial state: #.x#").unwrap_err().column, 18);
+
+        let error = Puzzle::parse("initial state: #..#\n\n...## => #\n..x.. => #").err().unwrap();
+        assert_eq!((error.line, error.column), (4, 3));
+    }
+
+    #[test]
+    fn next_generation_works() {
+        let notes = vec![create_note("..#.. => #").unwrap(), create_note(".#... => #").unwrap()];
+        let mut plants = load_plants_from_str("#...#", 0);
+
+        // the lone plants stay, and each spreads one pot to the right
+        assert_eq!(next_generation(&notes, &mut plants), vec![1, 5]);
+        assert_eq!(calculate_count(&plants), 10);
+    }
+
+    #[test]
+    fn simulate_works() {
+        let puzzle = Puzzle::parse(EXAMPLES[0].input).unwrap();
+        let mut pots = puzzle.simulate().unwrap();
+
+        for _ in 0..20 {
+            assert!(pots.step());
+        }
+
+        assert_eq!(pots.metrics()[0], ("total", 325));
+        assert_eq!(pots.view(200)[0], "pots from -2:");
+
+        let snapshot = pots.snapshot();
+        assert_eq!((snapshot.get("first"), snapshot.get("pots")), (Ok("-2"), Ok("#....##....#####...#######....#.#..##")));
+
+        let mut resumed = puzzle.resume(&snapshot).unwrap();
+        for _ in 0..50 {
+            pots.step();
+            resumed.step();
+        }
+        assert_eq!((resumed.tick(), resumed.metrics()), (pots.tick(), pots.metrics()));
+
+        let broken = Snapshot::parse("aoc snapshot\nday 12\ntick 3\nfirst -2\ndiff 0\npots ..#x").unwrap();
+        assert!(puzzle.resume(&broken).is_err());
+    }
+
+    #[test]
+    fn char_to_state_works() {
+        assert_eq!(char_to_state('.'), PlantState::Empty);
+        assert_eq!(char_to_state('#'), PlantState::Filled);
+    }
+}
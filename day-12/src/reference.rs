@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Stops part 2 after 300 generations, since it's simulated generation by generation.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[("generations", "300")], max_size: 100 };
+
+// the pots with a plant in them, and the patterns that grow one
+fn garden(input: &str) -> (HashSet<i64>, HashSet<String>) {
+    let mut lines = input.lines();
+    let initial_state = lines.next().unwrap().trim_start_matches("initial state: ");
+
+    let plants = initial_state.chars().enumerate().filter(|&(_, pot)| pot == '#').map(|(i, _)| i as i64).collect();
+    let growing = lines.filter(|line| line.ends_with("=> #")).map(|line| line[..5].to_string()).collect();
+
+    return (plants, growing);
+}
+
+fn sum_after(input: &str, generations: i64) -> i64 {
+    let (mut plants, growing) = garden(input);
+
+    for _generation in 0..generations {
+        let low = plants.iter().min().cloned().unwrap_or(0) - 2;
+        let high = plants.iter().max().cloned().unwrap_or(0) + 2;
+
+        plants = (low..=high)
+            .filter(|pot| {
+                let pattern: String = (pot - 2..=pot + 2).map(|p| if plants.contains(&p) { '#' } else { '.' }).collect();
+                growing.contains(&pattern)
+            })
+            .collect();
+    }
+
+    return plants.iter().sum();
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    return Answer::from(sum_after(input, 20));
+}
+
+fn part_2(input: &str, params: &Params) -> Answer {
+    return Answer::from(sum_after(input, params.get("generations", 50_000_000_000).unwrap()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
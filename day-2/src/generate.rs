@@ -0,0 +1,45 @@
+use common::rng::Rng;
+
+/// How many box ids `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 250;
+
+const ID_LENGTH: usize = 26;
+
+/// `size` box ids of lowercase letters, all the same length. One pair differs
+/// by a single letter like the puzzle promises, the rest are random enough
+/// that another such pair is vanishingly unlikely.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let mut ids: Vec<String> = (0..size.max(2) - 1)
+        .map(|_| (0..ID_LENGTH).map(|_| *rng.pick(&letters)).collect())
+        .collect();
+
+    let mut twin: Vec<char> = ids[0].chars().collect();
+    let index = rng.below(ID_LENGTH as u64) as usize;
+    while twin[index] == ids[0].as_bytes()[index] as char {
+        twin[index] = *rng.pick(&letters);
+    }
+
+    ids.push(twin.into_iter().collect());
+    rng.shuffle(&mut ids);
+
+    return ids.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 40);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.lines().count(), 40);
+            puzzle.part_1();
+            assert_eq!(puzzle.part_2().to_string().len(), ID_LENGTH - 1);
+        }
+    }
+}
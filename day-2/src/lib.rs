@@ -0,0 +1,204 @@
+//! Day 2: Inventory Management System. Part 1 is a checksum over the box
+//! ids and part 2 is the letters shared by the two ids that differ in exactly
+//! one place.
+//!
+//! ```
+//! let box_ids = vec![String::from("abcde"), String::from("fghij"), String::from("fguij")];
+//!
+//! let (box_a, box_b) = day_2::find_correct_box_ids(&box_ids).unwrap();
+//! assert_eq!(day_2::common_letters(&box_a, &box_b), "fgij");
+//! ```
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use common::parser::{chars_while, parse_line};
+use common::{parse_lines, Answer, Example, ParseError, Solution};
+
+/// Box ids of random letters with one pair planted a letter apart.
+pub mod generate;
+/// Counts letters by rescanning each id, and compares every id with every other one for part 2.
+pub mod reference;
+
+/// The box ids, all the same length.
+pub struct Puzzle {
+    box_ids: Vec<String>,
+}
+
+/// The puzzle text's two lists of box ids, one for each part.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "checksum",
+        input: "abcdef\n\
+                bababc\n\
+                abbcde\n\
+                abcccd\n\
+                aabcdd\n\
+                abcdee\n\
+                ababab",
+        params: &[],
+        part_1: Some("12"),
+        part_2: None,
+    },
+    Example {
+        name: "common letters",
+        input: "abcde\n\
+                fghij\n\
+                klmno\n\
+                pqrst\n\
+                fguij\n\
+                axcye\n\
+                wvxyz",
+        params: &[],
+        part_1: None,
+        part_2: Some("fgij"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let box_ids = parse_lines(input, parse_box_id)?;
+
+        // comparing ids character by character only works if they all line up
+        if let Some(first) = box_ids.first() {
+            for (index, box_id) in box_ids.iter().enumerate() {
+                if box_id.len() != first.len() {
+                    let expected = format!("a box id of {} letters like the first one", first.len());
+                    let column = box_id.len().min(first.len()) + 1;
+
+                    return Err(ParseError::new(&expected, box_id, column).at_line(index + 1));
+                }
+            }
+        }
+
+        Ok(Puzzle { box_ids })
+    }
+
+    fn part_1(&self) -> Answer {
+        return Answer::from(checksum(&self.box_ids));
+    }
+
+    fn part_2(&self) -> Answer {
+        match find_correct_box_ids(&self.box_ids) {
+            Some((box_a, box_b)) => Answer::Text(common_letters(&box_a, &box_b)),
+            None => Answer::Unsolved(String::from("no box ids differ by exactly one character")),
+        }
+    }
+}
+
+/// How many ids have a letter exactly twice, times how many have one exactly
+/// three times.
+pub fn checksum(box_ids: &[String]) -> usize {
+    let mut pairs = 0;
+    let mut triplets = 0;
+
+    for str in box_ids.iter() {
+        let mut hash = HashMap::new();
+
+        for id in str.chars() {
+            match hash.get(&id) {
+                Some(count) => hash.insert(id, count + 1),
+                _ => hash.insert(id, 1)
+            };
+        }
+
+        let mut has_pair = false;
+        let mut has_triplet = false;
+
+        for val in hash.values() {
+            if val == &2 {
+                has_pair = true;
+            } else if val == &3 {
+                has_triplet = true;
+            }
+        }
+
+        if has_pair {
+            pairs += 1;
+        }
+        if has_triplet {
+            triplets += 1;
+        }
+    }
+
+    return pairs * triplets;
+}
+
+/// One box id, which is only lowercase letters.
+pub fn parse_box_id(line: &str) -> Result<String, ParseError> {
+    return parse_line(line, chars_while("a lowercase letter", |c| c.is_ascii_lowercase()));
+}
+
+/// The first two ids that differ by a single letter: going down the list, the
+/// first id that's a letter away from one before it, and that earlier id.
+pub fn find_correct_box_ids(all_box_ids: &[String]) -> Option<(String, String)> {
+    let mut box_ids: Vec<&str> = vec![];
+
+    for str in all_box_ids.iter() {
+        for box_id in &box_ids {
+            let mut num_of_different_indexs = 0;
+            let mut current_chars = str.chars();
+
+            for char_a in box_id.chars() {
+                if char_a != current_chars.next().unwrap() {
+                    num_of_different_indexs += 1;
+                }
+            }
+
+            if num_of_different_indexs == 1 {
+                return Some((box_id.to_string(), str.to_string()));
+            }
+        }
+
+        box_ids.push(str);
+    }
+
+    return None;
+}
+
+/// The letters two ids have in the same places.
+pub fn common_letters(box_a: &str, box_b: &str) -> String {
+    return box_a
+        .chars()
+        .zip(box_b.chars())
+        .filter(|(a, b)| a == b)
+        .map(|(a, _b)| a)
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Puzzle::parse("abcde\nab1de").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = Puzzle::parse("abcde\nabcdef").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
+    #[test]
+    fn common_letters_works() {
+        assert_eq!(common_letters("fghij", "fguij"), "fgij");
+    }
+
+    #[test]
+    fn find_correct_box_ids_finds_the_first_pair() {
+        let box_ids: Vec<String> = ["abcd", "abce", "wxyz", "wxyy"].iter().map(|id| id.to_string()).collect();
+        assert_eq!(find_correct_box_ids(&box_ids), Some((String::from("abcd"), String::from("abce"))));
+    }
+
+    #[test]
+    fn no_match_is_unsolved() {
+        let puzzle = Puzzle::parse("abcde\nfghij\nabcde").unwrap();
+        assert_eq!(puzzle.part_2(), Answer::Unsolved(String::from("no box ids differ by exactly one character")));
+    }
+}
@@ -0,0 +1,48 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given up to 200 ids, since part 2 compares every pair.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 200 };
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let ids: Vec<&str> = input.lines().collect();
+
+    let has_count = |id: &str, count: usize| id.chars().any(|c| id.chars().filter(|&other| other == c).count() == count);
+    let pairs = ids.iter().filter(|id| has_count(id, 2)).count();
+    let triplets = ids.iter().filter(|id| has_count(id, 3)).count();
+
+    return Answer::from(pairs * triplets);
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    let ids: Vec<&str> = input.lines().collect();
+
+    for a in ids.iter() {
+        for b in ids.iter() {
+            let differences = a.chars().zip(b.chars()).filter(|(x, y)| x != y).count();
+
+            if differences == 1 {
+                let common: String = a.chars().zip(b.chars()).filter(|(x, y)| x == y).map(|(x, _y)| x).collect();
+                return Answer::from(common);
+            }
+        }
+    }
+
+    return Answer::Unsolved(String::from("no box ids differ by exactly one character"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
@@ -0,0 +1,40 @@
+use common::rng::Rng;
+
+/// How many claims `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 1000;
+
+const FABRIC_SIZE: i64 = 1000;
+
+/// `size` claims on the 1000 inch square of fabric, numbered from 1.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut claims = vec![];
+
+    for id in 1..=size.max(1) {
+        let width = rng.range(1, 30);
+        let height = rng.range(1, 30);
+        let left = rng.range(0, FABRIC_SIZE - width);
+        let top = rng.range(0, FABRIC_SIZE - height);
+
+        claims.push(format!("#{} @ {},{}: {}x{}", id, left, top, width, height));
+    }
+
+    return claims.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..5 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.lines().count(), 100);
+            puzzle.part_1();
+            puzzle.part_2();
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Day 3: No Matter How You Slice It. Elves claim rectangles of one big
+//! piece of fabric, part 1 counts the square inches claimed more than once and
+//! part 2 finds the one claim that overlaps nothing.
+//!
+//! ```
+//! let claims = vec![day_3::convert_line_to_variables("#1 @ 1,3: 4x4").unwrap(), (2, 3, 1, 4, 4)];
+//! let (fabric, claim_ids) = day_3::claim_fabric(&claims);
+//!
+//! assert_eq!(fabric.values().filter(|claims| claims.len() > 1).count(), 4);
+//! assert_eq!(claim_ids, vec![1, 2]);
+//! ```
+
+#![warn(missing_docs)]
+
+use common::parser::{literal, parse_line, unsigned, Input, Parsed, Parser};
+use common::{parse_lines, Answer, Example, ParseError, Solution};
+use grid::image::ColourMap;
+use grid::{Image, Point, SparseGrid};
+
+/// Claims placed at random on the 1000 inch square of fabric.
+pub mod generate;
+/// Counts claims on a plain 1000 by 1000 array instead of a map of plots.
+pub mod reference;
+
+// the most square inches all the claims can cover between them, the real input claims about half a million
+const MAX_CLAIMED: u64 = 4_000_000;
+
+/// A claim's id, left offset, top offset, width and height.
+pub type Claim = (u16, u16, u16, u16, u16);
+
+/// Every elf's claim.
+pub struct Puzzle {
+    claims: Vec<Claim>,
+}
+
+/// The puzzle text's three claims.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "claims",
+        input: "#1 @ 1,3: 4x4\n\
+                #2 @ 3,1: 4x4\n\
+                #3 @ 5,5: 2x2",
+        params: &[],
+        part_1: Some("4"),
+        part_2: Some("3"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle { claims: parse_lines(input, convert_line_to_variables)? })
+    }
+
+    fn check_size(&self) -> Result<(), String> {
+        let claimed: u64 = self.claims.iter().map(|&(_id, _left, _top, width, height)| u64::from(width) * u64::from(height)).sum();
+
+        if claimed > MAX_CLAIMED {
+            return Err(format!("the claims cover {} square inches, more than the {} allowed", claimed, MAX_CLAIMED));
+        }
+
+        return Ok(());
+    }
+
+    fn part_1(&self) -> Answer {
+        let (fabric, _all_claim_ids) = claim_fabric(&self.claims);
+
+        return Answer::from(fabric.values().filter(|claims| claims.len() > 1).count());
+    }
+
+    fn part_2(&self) -> Answer {
+        let (fabric, mut all_claim_ids) = claim_fabric(&self.claims);
+
+        for val in fabric.values() {
+            if val.len() > 1 {
+                for claim in val.iter() {
+                    if let Some(e) = all_claim_ids.iter().position(|&n| n == *claim) {
+                        all_claim_ids.remove(e);
+                    }
+                }
+            }
+        }
+
+        // the puzzle promises exactly one, anything else has no answer
+        match all_claim_ids.as_slice() {
+            [claim_id] => Answer::from(u32::from(*claim_id)),
+            [] => Answer::Unsolved(String::from("every claim overlaps another")),
+            claim_ids => Answer::Unsolved(format!("{} claims don't overlap any other", claim_ids.len())),
+        }
+    }
+
+    // how many claims cover each square inch, hotter where more of them overlap
+    fn render(&self) -> Option<Image> {
+        let (fabric, _all_claim_ids) = claim_fabric(&self.claims);
+        let most_claims = fabric.values().map(|claims| claims.len()).max()?;
+
+        return Some(Image::from_fn(fabric.bounds()?, |plot| match fabric.get(plot) {
+            Some(claims) => ColourMap::Heat.between(claims.len() as f64, 0.0, most_claims as f64),
+            None => ColourMap::Heat.at(0.0),
+        }));
+    }
+}
+
+/// Which claims cover each square inch, only the inches that are claimed at
+/// all, and every claim's id in the order given.
+pub fn claim_fabric(claims: &[Claim]) -> (SparseGrid<Vec<u16>>, Vec<u16>) {
+    let mut fabric = SparseGrid::new();
+    let mut all_claim_ids = vec![];
+
+    for &(claim_id, left_offset, top_offset, width, height) in claims.iter() {
+        let plots = dimensions_to_plots(left_offset, top_offset, width, height);
+        all_claim_ids.push(claim_id);
+
+        for plot in plots {
+            fabric.get_or_insert_with(plot, Vec::new).push(claim_id);
+        }
+    }
+
+    return (fabric, all_claim_ids);
+}
+
+/// One claim, like `#1 @ 483,830: 24x18`.
+pub fn convert_line_to_variables(str: &str) -> Result<Claim, ParseError> {
+    return parse_line(str, claim);
+}
+
+fn claim(input: &mut Input) -> Parsed<Claim> {
+    literal("#").parse(input)?;
+    let claim_id = unsigned().parse(input)?;
+    literal(" @ ").parse(input)?;
+    let left_offset = unsigned().parse(input)?;
+    literal(",").parse(input)?;
+    let top_offset = unsigned().parse(input)?;
+    literal(": ").parse(input)?;
+    let width = unsigned().parse(input)?;
+    literal("x").parse(input)?;
+    let height = unsigned().parse(input)?;
+
+    return Ok((claim_id, left_offset, top_offset, width, height));
+}
+
+/// Every square inch of a rectangle, column by column.
+pub fn dimensions_to_plots(left_offset: u16, top_offset: u16, width: u16, height: u16) -> Vec<Point> {
+    let mut plots = vec![];
+
+    // wider than u16 so a claim can reach past the 65535th inch
+    let (left_offset, top_offset) = (i64::from(left_offset), i64::from(top_offset));
+
+    for x in left_offset..(left_offset + i64::from(width)) {
+        for y in top_offset..(top_offset + i64::from(height)) {
+            plots.push(Point::new(x, y));
+        }
+    }
+
+    return plots;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn test_convert_line_to_variables() {
+        assert_eq!(convert_line_to_variables("#1 @ 483,830: 24x18"), Ok((1, 483, 830, 24, 18)));
+        assert_eq!(convert_line_to_variables("#1 @ 483,830 24x18").unwrap_err().column, 13);
+        assert_eq!(convert_line_to_variables("#1 @ 483,830: 24x").unwrap_err().expected, "a number");
+    }
+
+    #[test]
+    fn render_works() {
+        let image = Puzzle::parse(EXAMPLES[0].input).unwrap().render().unwrap();
+
+        assert_eq!((image.width(), image.height()), (6, 6));
+        assert_eq!(image.pixel(2, 2), ColourMap::Heat.at(1.0));
+    }
+
+    #[test]
+    fn no_single_claim_is_unsolved() {
+        let puzzle = Puzzle::parse("#1 @ 1,1: 2x2\n#2 @ 2,2: 2x2").unwrap();
+        assert_eq!(puzzle.part_2(), Answer::Unsolved(String::from("every claim overlaps another")));
+
+        let puzzle = Puzzle::parse("#1 @ 1,1: 2x2\n#2 @ 5,5: 2x2").unwrap();
+        assert_eq!(puzzle.part_2(), Answer::Unsolved(String::from("2 claims don't overlap any other")));
+    }
+
+    #[test]
+    fn check_size_works() {
+        assert_eq!(Puzzle::parse(EXAMPLES[0].input).unwrap().check_size(), Ok(()));
+        assert!(Puzzle::parse("#1 @ 0,0: 65535x65535").unwrap().check_size().is_err());
+    }
+
+    #[test]
+    fn test_dimensions_to_plots() {
+        assert_eq!(dimensions_to_plots(0, 0, 1, 2), vec!(Point::new(0, 0), Point::new(0, 1)));
+        assert_eq!(dimensions_to_plots(65535, 0, 1, 1), vec!(Point::new(65535, 0)));
+    }
+}
@@ -0,0 +1,76 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given up to 300 claims, since every one is painted onto the whole array.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 300 };
+
+const FABRIC: usize = 1000;
+
+// (id, left, top, width, height)
+fn claims(input: &str) -> Vec<(usize, usize, usize, usize, usize)> {
+    return input
+        .lines()
+        .map(|line| {
+            let numbers: Vec<usize> = line
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|number| !number.is_empty())
+                .map(|number| number.parse().unwrap())
+                .collect();
+
+            (numbers[0], numbers[1], numbers[2], numbers[3], numbers[4])
+        })
+        .collect();
+}
+
+fn claimed(claims: &[(usize, usize, usize, usize, usize)]) -> Vec<Vec<u32>> {
+    let mut fabric = vec![vec![0; FABRIC]; FABRIC];
+
+    for &(_id, left, top, width, height) in claims.iter() {
+        for column in fabric.iter_mut().skip(left).take(width) {
+            for count in column.iter_mut().skip(top).take(height) {
+                *count += 1;
+            }
+        }
+    }
+
+    return fabric;
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let fabric = claimed(&claims(input));
+
+    return Answer::from(fabric.iter().flatten().filter(|&&count| count > 1).count());
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    let claims = claims(input);
+    let fabric = claimed(&claims);
+
+    let alone: Vec<usize> = claims
+        .iter()
+        .filter(|&&(_, left, top, width, height)| (left..left + width).all(|x| (top..top + height).all(|y| fabric[x][y] == 1)))
+        .map(|&(id, ..)| id)
+        .collect();
+
+    match alone.as_slice() {
+        [id] => Answer::from(*id),
+        [] => Answer::Unsolved(String::from("every claim overlaps another")),
+        ids => Answer::Unsolved(format!("{} claims don't overlap any other", ids.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
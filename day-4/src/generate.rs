@@ -0,0 +1,81 @@
+use common::rng::Rng;
+
+/// How many shifts `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 300;
+
+const DAYS_IN_MONTH: [u32; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+/// `size` shifts on consecutive days, shuffled the way the puzzle input is.
+/// The parser drops the year, so there are at most 365 of them, and shifts
+/// that start before midnight start on the day before.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let dates = dates_in_year();
+    let guards: Vec<i64> = (0..rng.range(2, 20)).map(|_| rng.range(1, 3500)).collect();
+    let mut events = vec![];
+
+    for day in 1..=size.clamp(1, dates.len() - 1) {
+        let guard = rng.pick(&guards);
+
+        if rng.chance(0.5) {
+            events.push(format!("[1518-{}-{} 23:{:02}] Guard #{} begins shift", dates[day - 1].0, dates[day - 1].1, rng.range(45, 59), guard));
+        } else {
+            events.push(format!("[1518-{}-{} 00:{:02}] Guard #{} begins shift", dates[day].0, dates[day].1, rng.range(0, 3), guard));
+        }
+
+        // the first day always has a nap so there is a sleepiest guard
+        let mut minute = rng.range(4, 20);
+        while minute < 58 && (day == 1 || rng.chance(0.7)) {
+            let wakes = rng.range(minute + 1, 59);
+            events.push(format!("[1518-{}-{} 00:{:02}] falls asleep", dates[day].0, dates[day].1, minute));
+            events.push(format!("[1518-{}-{} 00:{:02}] wakes up", dates[day].0, dates[day].1, wakes));
+
+            minute = wakes + rng.range(1, 15);
+            if day == 1 {
+                break;
+            }
+        }
+    }
+
+    rng.shuffle(&mut events);
+
+    return events.join("\n");
+}
+
+// every (month, day) of a year that isn't a leap year, as zero padded text
+fn dates_in_year() -> Vec<(String, String)> {
+    let mut dates = vec![];
+
+    for (month, days) in DAYS_IN_MONTH.iter().enumerate() {
+        for day in 1..=*days {
+            dates.push((format!("{:02}", month + 1), format!("{:02}", day)));
+        }
+    }
+
+    return dates;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 30);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.lines().filter(|line| line.ends_with("begins shift")).count(), 30);
+            puzzle.part_1();
+            puzzle.part_2();
+        }
+    }
+
+    #[test]
+    fn dates_in_year_works() {
+        let dates = dates_in_year();
+
+        assert_eq!(dates.len(), 365);
+        assert_eq!(dates[59], (String::from("03"), String::from("01")));
+    }
+}
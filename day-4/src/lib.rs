@@ -0,0 +1,439 @@
+//! Day 4: Repose Record. A jumbled log of guards starting shifts, falling
+//! asleep and waking up. Part 1 wants the guard who sleeps the most and their
+//! sleepiest minute, part 2 the guard most often asleep on the same minute.
+//!
+//! ```
+//! let timeline = day_4::load_timeline(
+//!     "[1518-11-01 00:25] wakes up\n\
+//!      [1518-11-01 00:00] Guard #10 begins shift\n\
+//!      [1518-11-01 00:05] falls asleep",
+//! )
+//! .unwrap();
+//! let guards = day_4::group_by_guard(&timeline);
+//!
+//! assert_eq!(day_4::calc_most_slept_minute(&guards[&10]), (5, 1));
+//! assert_eq!(day_4::calc_sleep_time(&guards[&10]), 20 * 60);
+//! ```
+
+#![warn(missing_docs)]
+
+extern crate chrono;
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::prelude::*;
+use common::parser::{alt, label, literal, map, parse_line, unsigned, Input, Parsed, Parser};
+use common::{parse_lines, Answer, Example, ParseError, Solution};
+
+/// Logs of shifts on consecutive days, naps included, shuffled like the real input.
+pub mod generate;
+/// Sorts the lines as text and tallies each guard's minutes in a plain array.
+pub mod reference;
+
+/// The log, sorted into the order things happened.
+pub struct Puzzle {
+    timeline: Vec<TimelineEvent>,
+}
+
+/// The puzzle text's five days of two guards.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "guards",
+        input: "[1518-11-01 00:00] Guard #10 begins shift\n\
+                [1518-11-01 00:05] falls asleep\n\
+                [1518-11-01 00:25] wakes up\n\
+                [1518-11-01 00:30] falls asleep\n\
+                [1518-11-01 00:55] wakes up\n\
+                [1518-11-01 23:58] Guard #99 begins shift\n\
+                [1518-11-02 00:40] falls asleep\n\
+                [1518-11-02 00:50] wakes up\n\
+                [1518-11-03 00:05] Guard #10 begins shift\n\
+                [1518-11-03 00:24] falls asleep\n\
+                [1518-11-03 00:29] wakes up\n\
+                [1518-11-04 00:02] Guard #99 begins shift\n\
+                [1518-11-04 00:36] falls asleep\n\
+                [1518-11-04 00:46] wakes up\n\
+                [1518-11-05 00:03] Guard #99 begins shift\n\
+                [1518-11-05 00:45] falls asleep\n\
+                [1518-11-05 00:55] wakes up",
+        params: &[],
+        part_1: Some("240"),
+        part_2: Some("4455"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle { timeline: load_timeline(input)? })
+    }
+
+    fn part_1(&self) -> Answer {
+        let guards = group_by_guard(&self.timeline);
+
+        let mut most_sleep_amount = 0;
+        let mut sleepiest_guard = 0;
+
+        for (_key, guard) in guards.iter() {
+            let amount_of_sleep = calc_sleep_time(guard);
+
+            if amount_of_sleep > most_sleep_amount {
+                sleepiest_guard = guard.id;
+                most_sleep_amount = amount_of_sleep;
+            }
+        }
+
+        let guard = match guards.get(&sleepiest_guard) {
+            Some(guard) if most_sleep_amount > 0 => guard,
+            _ => return Answer::Unsolved(String::from(NOBODY_SLEEPS)),
+        };
+        let (sleepiest_minute, _count) = calc_most_slept_minute(guard);
+
+        return Answer::from(sleepiest_guard * sleepiest_minute);
+    }
+
+    fn part_2(&self) -> Answer {
+        let guards = group_by_guard(&self.timeline);
+        if guards.values().all(|guard| calc_sleep_time(guard) == 0) {
+            return Answer::Unsolved(String::from(NOBODY_SLEEPS));
+        }
+
+        let (id, minute) = calc_most_slept_same_minute(guards);
+
+        return Answer::from(id * minute);
+    }
+}
+
+// both parts are about naps, so a log without any has no answer
+const NOBODY_SLEEPS: &str = "no guard ever falls asleep";
+
+/// Every line of the log, sorted by when it happened. Once sorted it has to
+/// start with a shift, and each guard has to wake up from every nap before
+/// falling asleep again or handing over to the next shift.
+pub fn load_timeline(input: &str) -> Result<Vec<TimelineEvent>, ParseError> {
+    let mut timeline: Vec<(usize, TimelineEvent)> = parse_lines(input, TimelineEvent::new)?.into_iter().enumerate().collect();
+
+    // stable, so events at the same time stay in the order they're listed
+    timeline.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+    let lines: Vec<&str> = input.lines().collect();
+    let out_of_order = |index: usize, expected: &str| ParseError::new(expected, lines[index], 20).at_line(index + 1);
+
+    let mut on_shift = false;
+    let mut asleep_since = None;
+
+    for (index, event) in timeline.iter() {
+        match (&event.event, on_shift, asleep_since) {
+            (TimelineEventType::BeginsShift(_), _, None) => on_shift = true,
+            (TimelineEventType::BeginsShift(_), _, Some(_)) => return Err(out_of_order(*index, "wakes up before the next shift begins")),
+            (_, false, _) => return Err(out_of_order(*index, "Guard #<id> begins shift before anything else happens")),
+            (TimelineEventType::FallsAsleep, true, None) => asleep_since = Some(*index),
+            (TimelineEventType::FallsAsleep, true, Some(_)) => return Err(out_of_order(*index, "wakes up before falling asleep again")),
+            (TimelineEventType::WakesUp, true, Some(_)) => asleep_since = None,
+            (TimelineEventType::WakesUp, true, None) => return Err(out_of_order(*index, "falls asleep before waking up")),
+        }
+    }
+
+    if let Some(index) = asleep_since {
+        return Err(out_of_order(index, "wakes up after falling asleep"));
+    }
+
+    return Ok(timeline.into_iter().map(|(_, event)| event).collect());
+}
+
+/// Each guard's naps, keyed and ordered by id so ties between guards (and
+/// between minutes, below) go to the lowest. A guard's shift starts aren't
+/// among their events.
+pub fn group_by_guard(timeline: &[TimelineEvent]) -> BTreeMap<u32, Guard<'_>> {
+    let mut guards = BTreeMap::new();
+
+    let mut last_guard_id = 0;
+    for event in timeline.iter() {
+        match event.event {
+            TimelineEventType::BeginsShift(guard_id) => {
+                last_guard_id = guard_id;
+
+                guards.entry(guard_id).or_insert(Guard {
+                    id: last_guard_id,
+                    timeline_events: vec![],
+                });
+            }
+            _ => {
+                if let Some(guard) = guards.get_mut(&last_guard_id) {
+                    guard.timeline_events.push(event);
+                }
+            }
+        };
+    }
+
+    return guards;
+}
+
+/// What a line of the log says happened.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum TimelineEventType {
+    /// The guard on shift falls asleep.
+    FallsAsleep,
+    /// The guard on shift wakes up.
+    WakesUp,
+    /// The guard with this id starts their shift.
+    BeginsShift(u32),
+}
+
+/// One guard and what they got up to over every shift.
+pub struct Guard<'a> {
+    /// The guard's id, from `Guard #<id> begins shift`.
+    pub id: u32,
+    /// When the guard fell asleep and woke up, in order.
+    pub timeline_events: Vec<&'a TimelineEvent>,
+}
+
+/// One line of the log. Events are compared and ordered only by when they
+/// happened.
+#[derive(Eq, Clone)]
+pub struct TimelineEvent {
+    /// Seconds since the Unix epoch, with 1971 standing in for the year.
+    pub timestamp: i64,
+    /// The minute of the hour, which is all that matters for naps after midnight.
+    pub minute: u32,
+    /// What happened.
+    pub event: TimelineEventType,
+}
+
+impl TimelineEvent {
+    /// Parses a line like `[1518-11-01 00:05] falls asleep`.
+    pub fn new(raw_input: &str) -> Result<TimelineEvent, ParseError> {
+        let (month, day, hour, minute, timelint_event) = parse_line(raw_input, event)?;
+
+        let date_time = Utc
+            .ymd_opt(
+                1971, // The original years were 1518 which would result in a negative timestamp
+                month,
+                day,
+            )
+            .single()
+            .and_then(|date| date.and_hms_opt(hour, minute, 0));
+
+        match date_time {
+            Some(date_time) => {
+                return Ok(TimelineEvent {
+                    event: timelint_event,
+                    minute,
+                    timestamp: date_time.timestamp(),
+                });
+            }
+            None => return Err(ParseError::new("a real date and time", raw_input, 2)),
+        }
+    }
+
+    /// Seconds since `previous_event`. This doesn't handle events on
+    /// different days or in the wrong order.
+    pub fn time_between(&self, previous_event: &TimelineEvent) -> i64 {
+        return self.timestamp - previous_event.timestamp;
+    }
+}
+
+// [1518-11-01 00:05] falls asleep, as month, day, hour, minute and what happened
+fn event(input: &mut Input) -> Parsed<(u32, u32, u32, u32, TimelineEventType)> {
+    literal("[").parse(input)?;
+    unsigned::<i32>().parse(input)?;
+    literal("-").parse(input)?;
+    let month = unsigned().parse(input)?;
+    literal("-").parse(input)?;
+    let day = unsigned().parse(input)?;
+    literal(" ").parse(input)?;
+    let hour = unsigned().parse(input)?;
+    literal(":").parse(input)?;
+    let minute = unsigned().parse(input)?;
+    literal("] ").parse(input)?;
+
+    let expected = "falls asleep, wakes up or Guard #<id> begins shift";
+    let event_type = label(
+        expected,
+        alt(
+            map(literal("falls asleep"), |_| TimelineEventType::FallsAsleep),
+            alt(map(literal("wakes up"), |_| TimelineEventType::WakesUp), begins_shift),
+        ),
+    )
+    .parse(input)?;
+
+    return Ok((month, day, hour, minute, event_type));
+}
+
+fn begins_shift(input: &mut Input) -> Parsed<TimelineEventType> {
+    literal("Guard #").parse(input)?;
+    let guard_id = unsigned().parse(input)?;
+    literal(" begins shift").parse(input)?;
+
+    return Ok(TimelineEventType::BeginsShift(guard_id));
+}
+
+impl PartialOrd for TimelineEvent {
+    fn partial_cmp(&self, other: &TimelineEvent) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimelineEvent {
+    fn cmp(&self, other: &TimelineEvent) -> Ordering {
+        let difference = self.time_between(other);
+
+        if difference < 0 {
+            return Ordering::Less;
+        } else if difference > 0 {
+            return Ordering::Greater;
+        }
+
+        return Ordering::Equal;
+    }
+}
+
+impl PartialEq for TimelineEvent {
+    fn eq(&self, other: &TimelineEvent) -> bool {
+        return self.timestamp == other.timestamp;
+    }
+}
+
+impl fmt::Display for TimelineEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:?}", self.timestamp, self.event)
+    }
+}
+
+/// How many seconds a guard slept in total.
+pub fn calc_sleep_time(guard: &Guard) -> i64 {
+    let mut last_sleep_event: Option<&TimelineEvent> = Option::None;
+    let mut current_sleep_amount = 0;
+
+    for event in guard.timeline_events.iter() {
+        match event.event {
+            TimelineEventType::FallsAsleep => last_sleep_event = Some(event),
+            TimelineEventType::WakesUp => {
+                if let Some(asleep) = last_sleep_event {
+                    current_sleep_amount += event.time_between(asleep);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    return current_sleep_amount;
+}
+
+/// The id of the guard who was asleep on the same minute most often, and
+/// that minute.
+pub fn calc_most_slept_same_minute(guards: BTreeMap<u32, Guard>) -> (u32, u32) {
+    let mut most_slept_minute = 0;
+    let mut num_of_times_slept_on_minute = 0;
+    let mut guard_id_who_slept_same_minute = 0;
+
+    for (_guard_id, guard) in guards {
+        let (minute, count) = calc_most_slept_minute(&guard);
+
+        if count > num_of_times_slept_on_minute {
+            most_slept_minute = minute;
+            num_of_times_slept_on_minute = count;
+            guard_id_who_slept_same_minute = guard.id;
+        }
+    }
+
+    return (guard_id_who_slept_same_minute, most_slept_minute);
+}
+
+/// The minute a guard was asleep on most often, and how many times.
+pub fn calc_most_slept_minute(guard: &Guard) -> (u32, u32) {
+    let mut last_sleep_event: Option<&TimelineEvent> = Option::None;
+    let mut time_map = BTreeMap::new();
+
+    for event in guard.timeline_events.iter() {
+        match event.event {
+            TimelineEventType::FallsAsleep => last_sleep_event = Some(event),
+            TimelineEventType::WakesUp => {
+                // load_timeline makes sure there is one, but a hand built guard might not
+                let start = last_sleep_event.map_or(event.minute, |asleep| asleep.minute);
+                let end = event.minute;
+
+                for minute in start..end {
+                    match time_map.get(&minute) {
+                        Some(time) => {
+                            time_map.insert(minute, time + 1);
+                        }
+                        None => {
+                            time_map.insert(minute, 1);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut num_of_times_slept_at_minute = 0;
+    let mut most_slept_minute = 0;
+
+    for (minute_key, count_value) in time_map {
+        if count_value > num_of_times_slept_at_minute {
+            most_slept_minute = minute_key;
+            num_of_times_slept_at_minute = count_value;
+        }
+    }
+
+    return (most_slept_minute, num_of_times_slept_at_minute);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn timeline_event_new_works() {
+        let event = TimelineEvent::new("[1518-11-01 00:05] falls asleep").unwrap();
+        assert_eq!(event.event, TimelineEventType::FallsAsleep);
+        assert_eq!(event.minute, 5);
+
+        let event = TimelineEvent::new("[1518-11-01 23:58] Guard #99 begins shift").unwrap();
+        assert_eq!(event.event, TimelineEventType::BeginsShift(99));
+    }
+
+    #[test]
+    fn timeline_event_new_errors() {
+        assert_eq!(TimelineEvent::new("1518-11-01 00:05 falls asleep").err().unwrap().column, 1);
+        assert_eq!(TimelineEvent::new("[1518-11-01 00:05] eats lunch").err().unwrap().column, 20);
+        assert_eq!(TimelineEvent::new("[1518-13-01 00:05] wakes up").err().unwrap().column, 2);
+    }
+
+    #[test]
+    fn load_timeline_errors() {
+        let error = |input: &str| {
+            let error = load_timeline(input).err().unwrap();
+            return (error.line, error.expected);
+        };
+
+        let shift = "[1518-11-01 00:00] Guard #10 begins shift";
+        assert_eq!(error(&format!("{}\n[1518-11-01 00:25] wakes up", shift)), (2, String::from("falls asleep before waking up")));
+        assert_eq!(
+            error(&format!("[1518-11-01 23:58] Guard #99 begins shift\n{}\n[1518-11-01 00:05] falls asleep", shift)),
+            (1, String::from("wakes up before the next shift begins"))
+        );
+        assert_eq!(error(&format!("[1518-10-31 23:05] falls asleep\n{}", shift)), (1, String::from("Guard #<id> begins shift before anything else happens")));
+        assert_eq!(
+            error(&format!("{}\n[1518-11-01 00:25] falls asleep\n[1518-11-01 00:05] falls asleep", shift)),
+            (2, String::from("wakes up before falling asleep again"))
+        );
+        assert_eq!(error(&format!("{}\n[1518-11-01 00:05] falls asleep", shift)), (2, String::from("wakes up after falling asleep")));
+    }
+
+    #[test]
+    fn no_naps_is_unsolved() {
+        let puzzle = Puzzle::parse("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-02 00:00] Guard #99 begins shift").unwrap();
+        let unsolved = Answer::Unsolved(String::from("no guard ever falls asleep"));
+
+        assert_eq!((puzzle.part_1(), puzzle.part_2()), (unsolved.clone(), unsolved));
+    }
+}
@@ -0,0 +1,110 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given up to 300 shifts.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 300 };
+
+// how often each guard was asleep at each minute past midnight, ordered by guard id
+fn sleep_minutes(input: &str) -> Vec<(u32, [u32; 60])> {
+    // the timestamps are zero padded, so sorting the text sorts the events
+    let mut lines: Vec<&str> = input.lines().collect();
+    lines.sort();
+
+    let mut guards: Vec<(u32, [u32; 60])> = vec![];
+    let mut guard = 0;
+    let mut fell_asleep = 0;
+
+    for line in lines {
+        let minute: usize = line[15..17].parse().unwrap();
+
+        if line.ends_with("begins shift") {
+            let id = line.split('#').nth(1).unwrap().split(' ').next().unwrap().parse().unwrap();
+
+            guard = match guards.iter().position(|&(other, _)| other == id) {
+                Some(index) => index,
+                None => {
+                    guards.push((id, [0; 60]));
+                    guards.len() - 1
+                }
+            };
+        } else if line.ends_with("falls asleep") {
+            fell_asleep = minute;
+        } else {
+            for asleep in fell_asleep..minute {
+                guards[guard].1[asleep] += 1;
+            }
+        }
+    }
+
+    guards.sort_by_key(|&(id, _)| id);
+
+    return guards;
+}
+
+// the first minute with the highest count
+fn sleepiest_minute(minutes: &[u32; 60]) -> (usize, u32) {
+    let mut best = (0, 0);
+
+    for (minute, &count) in minutes.iter().enumerate() {
+        if count > best.1 {
+            best = (minute, count);
+        }
+    }
+
+    return best;
+}
+
+// the solver's answer when there are no naps to go on
+fn nobody_sleeps(guards: &[(u32, [u32; 60])]) -> bool {
+    return guards.iter().all(|(_, minutes)| minutes.iter().all(|&count| count == 0));
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let guards = sleep_minutes(input);
+    if nobody_sleeps(&guards) {
+        return Answer::Unsolved(String::from("no guard ever falls asleep"));
+    }
+    let mut best = &guards[0];
+
+    for guard in guards.iter() {
+        if guard.1.iter().sum::<u32>() > best.1.iter().sum::<u32>() {
+            best = guard;
+        }
+    }
+
+    return Answer::from(best.0 as usize * sleepiest_minute(&best.1).0);
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    let guards = sleep_minutes(input);
+    if nobody_sleeps(&guards) {
+        return Answer::Unsolved(String::from("no guard ever falls asleep"));
+    }
+    let mut best = (0, 0, 0);
+
+    for (id, minutes) in guards.iter() {
+        let (minute, count) = sleepiest_minute(minutes);
+
+        if count > best.2 {
+            best = (*id as usize, minute, count);
+        }
+    }
+
+    return Answer::from(best.0 * best.1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
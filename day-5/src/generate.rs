@@ -0,0 +1,35 @@
+use common::rng::Rng;
+
+/// How many units long a polymer `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 10000;
+
+/// A polymer `size` units long. Only a handful of unit types are used so that
+/// neighbouring units react often enough to make reducing it worth testing.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let types: Vec<char> = ('a'..='z').take(rng.range(2, 8) as usize).collect();
+
+    return (0..size.max(1))
+        .map(|_| {
+            let unit = *rng.pick(&types);
+            if rng.chance(0.5) { unit.to_ascii_uppercase() } else { unit }
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 200);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.len(), 200);
+            puzzle.part_1();
+            puzzle.part_2();
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Day 5: Alchemical Reduction. Neighbouring units of the same type but
+//! opposite polarity (`a` and `A`) react and disappear. Part 1 is how long the
+//! polymer is once nothing is left to react, part 2 how short it gets with one
+//! unit type taken out first.
+//!
+//! ```
+//! assert_eq!(day_5::reduce_polymer(String::from("dabAcCaCBAcCcaDA")), "dabCBAcaDA");
+//! assert_eq!(day_5::find_index_of_pair("dabAcCaCBA"), 4);
+//! ```
+
+#![warn(missing_docs)]
+
+use common::parser::{chars_while, parse_line};
+use common::{Answer, Example, ParseError, Solution};
+
+/// Polymers made from only a few unit types, so plenty of them react.
+pub mod generate;
+/// Takes out the first reacting pair and searches again from the start until none are left.
+pub mod reference;
+
+/// The polymer, one letter per unit.
+pub struct Puzzle {
+    polymer: String,
+}
+
+/// The puzzle text's polymer.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "polymer",
+        input: "dabAcCaCBAcCcaDA",
+        params: &[],
+        part_1: Some("10"),
+        part_2: Some("4"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        // the polymer is a single line of units, each one a letter of either polarity
+        let polymer = parse_line(input.trim(), chars_while("a letter", |c| c.is_ascii_alphabetic()))?;
+
+        Ok(Puzzle { polymer })
+    }
+
+    fn part_1(&self) -> Answer {
+        return Answer::from(reduce_polymer(self.polymer.clone()).len());
+    }
+
+    fn part_2(&self) -> Answer {
+        let part_1_polymer = reduce_polymer(self.polymer.clone());
+
+        let units = vec![
+            'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+            's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+        ];
+
+        let mut shortest_polymer = part_1_polymer.len(); // must be shorter than doing "nothing"
+        for unit in units {
+            let cloned = self.polymer
+                .clone()
+                .replace([unit, unit.to_ascii_uppercase()], "");
+            let p = reduce_polymer(cloned);
+
+            if p.len() < shortest_polymer {
+                shortest_polymer = p.len();
+            }
+        }
+
+        return Answer::from(shortest_polymer);
+    }
+}
+
+/// The polymer after every reaction has happened. Units are added to the
+/// reduced polymer one at a time, and a unit that reacts with the last one
+/// takes it back off, so this is a single pass however much reacts.
+pub fn reduce_polymer(polymer: String) -> String {
+    let mut reduced: Vec<char> = Vec::with_capacity(polymer.len());
+
+    for unit in polymer.chars() {
+        match reduced.last() {
+            Some(&last) if last != unit && last.eq_ignore_ascii_case(&unit) => {
+                reduced.pop();
+            }
+            _ => reduced.push(unit),
+        }
+    }
+
+    return reduced.into_iter().collect();
+}
+
+/// Where the first pair of units that react starts, or -1 when nothing reacts.
+pub fn find_index_of_pair(poly: &str) -> isize {
+    let mut last_char = '0';
+    let mut found_index: isize = -1;
+
+    for (index, c) in poly.chars().enumerate() {
+        let uppercase = c.to_ascii_uppercase();
+        let lowercase = c.to_ascii_lowercase();
+
+        if (uppercase == last_char && c != uppercase) || (lowercase == last_char && c != lowercase) {
+            found_index = (index as isize) - 1;
+            break;
+        }
+
+        last_char = c;
+    }
+
+    return found_index;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::report::PartResult;
+    use std::time::Duration;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Puzzle::parse("dabA cCa").err().unwrap().column, 5);
+        assert_eq!(Puzzle::parse("dabAcCa\n").unwrap().polymer, "dabAcCa");
+    }
+
+    #[test]
+    fn reduce_polymer_works() {
+        assert_eq!(reduce_polymer(String::from("aA")), "");
+        assert_eq!(reduce_polymer(String::from("abBA")), "");
+        assert_eq!(reduce_polymer(String::from("aabAAB")), "aabAAB");
+        assert_eq!(reduce_polymer(String::from("aa")), "aa");
+    }
+
+    #[test]
+    fn reduce_polymer_is_quick() {
+        // every reaction is in the middle, which took a search from the start each time when this was quadratic
+        let polymer = format!("{}{}", "a".repeat(500_000), "A".repeat(500_000));
+        let result = PartResult::run(5, 1, Some(Duration::from_secs(10)), || Answer::from(reduce_polymer(polymer.clone()).len()));
+
+        assert_eq!(result.outcome, Ok(Answer::from(0)));
+    }
+}
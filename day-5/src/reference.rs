@@ -0,0 +1,46 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given polymers of up to 300 units, since every reaction starts the search over.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 300 };
+
+fn reacts(a: char, b: char) -> bool {
+    return a != b && a.eq_ignore_ascii_case(&b);
+}
+
+// removes one reacting pair at a time until there are none left
+fn reduce(mut units: Vec<char>) -> usize {
+    while let Some(index) = (1..units.len()).find(|&index| reacts(units[index - 1], units[index])) {
+        units.drain(index - 1..=index);
+    }
+
+    return units.len();
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    return Answer::from(reduce(input.trim().chars().collect()));
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    let shortest = ('a'..='z')
+        .map(|removed| reduce(input.trim().chars().filter(|unit| unit.to_ascii_lowercase() != removed).collect()))
+        .min();
+
+    return Answer::from(shortest.unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::HashSet;
+
+use common::rng::Rng;
+
+/// How many coordinates `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 50;
+
+/// `size` distinct coordinates, spread over a grid that grows with them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let extent = 10 + 8 * size as i64;
+    let mut seen = HashSet::new();
+    let mut coordinates = vec![];
+
+    while coordinates.len() < size {
+        let coordinate = (rng.range(0, extent), rng.range(0, extent));
+
+        if seen.insert(coordinate) {
+            coordinates.push(format!("{}, {}", coordinate.0, coordinate.1));
+        }
+    }
+
+    return coordinates.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..5 {
+            let input = generate(&mut Rng::new(seed), 10);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(input.lines().count(), 10);
+            puzzle.part_1();
+            puzzle.part_2();
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Day 6: Chronal Coordinates. Every location belongs to the coordinate
+//! closest to it by Manhattan distance. Part 1 is the largest area that isn't
+//! infinite, part 2 how many locations are close enough to all of them at once.
+//!
+//! ```
+//! use grid::Point;
+//!
+//! let coordinates = vec![Point::new(1, 1), Point::new(1, 6), Point::new(8, 3), Point::new(3, 4), Point::new(5, 5), Point::new(8, 9)];
+//! let areas = day_6::nearest_coordinates(&coordinates);
+//!
+//! assert_eq!(day_6::calculate_largest_area(&areas), 17);
+//! assert_eq!(day_6::count_within_safe_distance(&coordinates, 32), 16);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+use common::parser::{literal, parse_line, unsigned, Input, Parsed, Parser};
+use common::{parse_lines, Answer, Example, Params, ParseError, Solution};
+use grid::image::{self, Rgb};
+use grid::{Bounds, DenseGrid, Image, Point};
+
+/// Distinct coordinates spread over a grid that grows with how many there are.
+pub mod generate;
+/// Measures every location in the bounding box against every coordinate.
+pub mod reference;
+
+// part 2 counts the locations whose total distance to every coordinate is under this
+const SAFE_DISTANCE: i64 = 10000;
+
+// the most locations part 2 looks at, and the most distances it works out for them, the real
+// input needs about half a million and 25 million
+const MAX_AREA: i128 = 4_000_000;
+const MAX_DISTANCES: i128 = 100_000_000;
+
+/// The coordinates, and the total distance part 2 counts as safe.
+pub struct Puzzle {
+    coordinates: Vec<Point>,
+    safe_distance: i64,
+}
+
+/// The puzzle text's six coordinates, with its safe distance of 32.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "coordinates",
+        input: "1, 1\n\
+                1, 6\n\
+                8, 3\n\
+                3, 4\n\
+                5, 5\n\
+                8, 9",
+        params: &[("safe_distance", "32")],
+        part_1: Some("17"),
+        part_2: Some("16"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle { coordinates: parse_lines(input, parse_coordinate)?, safe_distance: SAFE_DISTANCE })
+    }
+
+    fn configure(&mut self, params: &Params) -> Result<(), String> {
+        self.safe_distance = params.get("safe_distance", self.safe_distance)?;
+        return Ok(());
+    }
+
+    // part 2's bounds are the biggest either part looks at, in i128 since a coordinate can be anywhere
+    fn check_size(&self) -> Result<(), String> {
+        let Bounds { min, max } = calculate_grid_bounds(&self.coordinates);
+        let margin = i128::from(self.safe_distance / self.coordinates.len().max(1) as i64) + 1;
+        let side = |min: i64, max: i64| (i128::from(max) - i128::from(min) + 1 + 2 * margin).max(0);
+
+        let area = side(min.x, max.x).checked_mul(side(min.y, max.y)).filter(|&area| area <= MAX_AREA);
+        let distances = area.and_then(|area| area.checked_mul(self.coordinates.len() as i128));
+
+        match distances {
+            Some(distances) if distances <= MAX_DISTANCES => Ok(()),
+            _ => Err(format!("the coordinates and safe_distance cover too much, at most {} locations and {} distances", MAX_AREA, MAX_DISTANCES)),
+        }
+    }
+
+    fn part_1(&self) -> Answer {
+        let areas = nearest_coordinates(&self.coordinates);
+
+        return Answer::from(calculate_largest_area(&areas));
+    }
+
+    fn part_2(&self) -> Answer {
+        return Answer::from(count_within_safe_distance(&self.coordinates, self.safe_distance));
+    }
+
+    // a colour per coordinate's area, with the coordinates themselves in black and ties in white
+    fn render(&self) -> Option<Image> {
+        let areas = nearest_coordinates(&self.coordinates);
+
+        return Some(Image::from_fn(areas.bounds(), |point| match areas.get(point).unwrap() {
+            _ if self.coordinates.contains(&point) => Rgb::BLACK,
+            Some(index) => image::category(*index),
+            None => Rgb::WHITE,
+        }));
+    }
+}
+
+/// One coordinate, like `1, 6`.
+pub fn parse_coordinate(str: &str) -> Result<Point, ParseError> {
+    return parse_line(str, coordinate);
+}
+
+// 1, 6
+fn coordinate(input: &mut Input) -> Parsed<Point> {
+    let x = unsigned().parse(input)?;
+    literal(", ").parse(input)?;
+    let y = unsigned().parse(input)?;
+
+    return Ok(Point::new(x, y));
+}
+
+fn calculate_grid_bounds(coordinates: &[Point]) -> Bounds {
+    return Bounds::of(coordinates.iter().cloned()).unwrap_or_else(|| Bounds::new(Point::default(), Point::default()));
+}
+
+/// The index of the one coordinate closest to `point`, None when several tie.
+pub fn calculate_nearest_neighbor(point: Point, coordinates: &[Point]) -> Option<usize> {
+    let mut shortest_distance = i64::MAX;
+    let mut nearest_neighbors = vec![];
+
+    for (index, coordinate) in coordinates.iter().enumerate() {
+        let distance = coordinate.manhattan(point);
+
+        if distance < shortest_distance {
+            shortest_distance = distance;
+
+            nearest_neighbors.clear();
+            nearest_neighbors.push(index);
+        } else if distance == shortest_distance {
+            nearest_neighbors.push(index);
+        }
+    }
+
+    if nearest_neighbors.len() != 1 {
+        return None;
+    }
+
+    return Some(nearest_neighbors[0]);
+}
+
+/// The nearest coordinate of every location inside their bounding box.
+pub fn nearest_coordinates(coordinates: &[Point]) -> DenseGrid<Option<usize>> {
+    let bounds = calculate_grid_bounds(coordinates);
+
+    return DenseGrid::from_fn(bounds, |point| calculate_nearest_neighbor(point, coordinates));
+}
+
+/// The size of the largest area that doesn't go on forever. An area that
+/// reaches the edge of the bounding box keeps going: stepping further out
+/// moves away from every coordinate by the same amount, so whichever one was
+/// nearest stays nearest.
+pub fn calculate_largest_area(areas: &DenseGrid<Option<usize>>) -> usize {
+    let bounds = areas.bounds();
+    let mut infinite = HashSet::new();
+    let mut sizes = vec![];
+
+    for (point, nearest) in areas.iter() {
+        if let Some(index) = *nearest {
+            if bounds.is_edge(point) {
+                infinite.insert(index);
+            }
+
+            if index >= sizes.len() {
+                sizes.resize(index + 1, 0);
+            }
+            sizes[index] += 1;
+        }
+    }
+
+    let finite = sizes.iter().enumerate().filter(|(index, _size)| !infinite.contains(index));
+
+    return finite.map(|(_index, size)| *size).max().unwrap_or(0);
+}
+
+/// How many locations have a total distance to every coordinate under
+/// `safe_distance`. The safe region can reach past the bounding box: a
+/// location k steps outside it is at least k away from every coordinate, so
+/// it can only be safe while k is under safe_distance / the number of
+/// coordinates.
+pub fn count_within_safe_distance(coordinates: &[Point], safe_distance: i64) -> usize {
+    let margin = safe_distance / coordinates.len().max(1) as i64 + 1;
+    let bounds = calculate_grid_bounds(coordinates).expand(margin);
+
+    return bounds.points().filter(|&point| calculate_within_safe_distance(point, coordinates, safe_distance)).count();
+}
+
+fn calculate_within_safe_distance(point: Point, coordinates: &[Point], safe_distance: i64) -> bool {
+    let running_count: i64 = coordinates.iter().map(|coordinate| coordinate.manhattan(point)).sum();
+
+    return running_count < safe_distance;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn it_works() {
+        let coordinates = vec![Point::new(0, 0)];
+        assert_eq!(calculate_grid_bounds(&coordinates), Bounds::new(Point::new(0, 0), Point::new(0, 0)));
+    }
+
+    #[test]
+    fn calculate_nearest_neighbor_works() {
+        let coordinates = vec![Point::new(1, 1), Point::new(1, 5), Point::new(8, 3)];
+
+        assert_eq!(calculate_nearest_neighbor(Point::new(0, 0), &coordinates), Some(0));
+        assert_eq!(calculate_nearest_neighbor(Point::new(1, 3), &coordinates), None);
+    }
+
+    #[test]
+    fn render_works() {
+        let image = Puzzle::parse(EXAMPLES[0].input).unwrap().render().unwrap();
+
+        assert_eq!((image.width(), image.height()), (8, 9));
+        assert_eq!(image.pixel(0, 0), Rgb::BLACK);
+        assert_eq!(image.pixel(4, 3), image::category(4));
+        assert_eq!(image.pixel(0, 3), Rgb::WHITE);
+    }
+
+    #[test]
+    fn check_size_works() {
+        let input = generate::generate(&mut Rng::new(1), generate::DEFAULT_SIZE);
+        let mut puzzle = Puzzle::parse(&input).unwrap();
+        assert_eq!(puzzle.check_size(), Ok(()));
+
+        // two coordinates with a ten billion location box between them
+        assert!(Puzzle::parse("0, 0\n100000, 100000").unwrap().check_size().is_err());
+
+        common::configure(&mut puzzle, &Params::from_pairs(&[("safe_distance", "10000000")])).unwrap();
+        assert!(puzzle.check_size().is_err());
+    }
+
+    #[test]
+    fn parse_coordinate_works() {
+        assert_eq!(parse_coordinate("1, 6"), Ok(Point::new(1, 6)));
+        assert_eq!(parse_coordinate("1 6").unwrap_err().column, 2);
+    }
+}
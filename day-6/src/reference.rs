@@ -0,0 +1,99 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Uses a safe distance of 200 and at most 30 coordinates. The real safe distance makes
+/// the region far too big to brute force.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[("safe_distance", "200")], max_size: 30 };
+
+fn coordinates(input: &str) -> Vec<(i64, i64)> {
+    return input
+        .lines()
+        .map(|line| {
+            let mut numbers = line.split(", ").map(|number| number.parse().unwrap());
+            (numbers.next().unwrap(), numbers.next().unwrap())
+        })
+        .collect();
+}
+
+fn distance(a: (i64, i64), b: (i64, i64)) -> i64 {
+    return (a.0 - b.0).abs() + (a.1 - b.1).abs();
+}
+
+// (min x, min y, max x, max y)
+fn bounds(coordinates: &[(i64, i64)]) -> (i64, i64, i64, i64) {
+    let xs = coordinates.iter().map(|c| c.0);
+    let ys = coordinates.iter().map(|c| c.1);
+
+    return (xs.clone().min().unwrap(), ys.clone().min().unwrap(), xs.max().unwrap(), ys.max().unwrap());
+}
+
+fn nearest(point: (i64, i64), coordinates: &[(i64, i64)]) -> Option<usize> {
+    let closest = coordinates.iter().map(|&c| distance(point, c)).min().unwrap();
+    let nearest: Vec<usize> = (0..coordinates.len()).filter(|&i| distance(point, coordinates[i]) == closest).collect();
+
+    if nearest.len() == 1 {
+        return Some(nearest[0]);
+    }
+
+    return None;
+}
+
+// an area touching the edge of the bounding box carries on forever
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let coordinates = coordinates(input);
+    let (min_x, min_y, max_x, max_y) = bounds(&coordinates);
+
+    let mut areas = vec![0; coordinates.len()];
+    let mut infinite = vec![false; coordinates.len()];
+
+    for x in min_x..=max_x {
+        for y in min_y..=max_y {
+            if let Some(owner) = nearest((x, y), &coordinates) {
+                areas[owner] += 1;
+
+                if x == min_x || y == min_y || x == max_x || y == max_y {
+                    infinite[owner] = true;
+                }
+            }
+        }
+    }
+
+    let largest = (0..coordinates.len()).filter(|&i| !infinite[i]).map(|i| areas[i]).max();
+
+    return Answer::from(largest.unwrap_or(0_usize));
+}
+
+// every safe location is closer than the safe distance to each coordinate
+fn part_2(input: &str, params: &Params) -> Answer {
+    let coordinates = coordinates(input);
+    let safe_distance = params.get("safe_distance", 10000_i64).unwrap();
+    let (min_x, min_y, max_x, max_y) = bounds(&coordinates);
+
+    let mut count = 0;
+
+    for x in min_x - safe_distance..=max_x + safe_distance {
+        for y in min_y - safe_distance..=max_y + safe_distance {
+            if coordinates.iter().map(|&c| distance((x, y), c)).sum::<i64>() < safe_distance {
+                count += 1;
+            }
+        }
+    }
+
+    return Answer::from(count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
@@ -0,0 +1,48 @@
+use common::rng::Rng;
+
+/// How many steps `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 26;
+
+/// Instructions between `size` steps (at most 26, one per letter). Steps are
+/// only ever made to wait on steps earlier in a shuffled order, so there is
+/// never a cycle, and every step after the first waits on at least one other.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut steps: Vec<char> = ('A'..='Z').take(size.clamp(2, 26)).collect();
+    rng.shuffle(&mut steps);
+
+    let mut instructions = vec![];
+
+    for later in 1..steps.len() {
+        let first = rng.below(later as u64) as usize;
+
+        for earlier in 0..later {
+            if earlier == first || rng.chance(0.15) {
+                instructions.push(format!(
+                    "Step {} must be finished before step {} can begin.",
+                    steps[earlier], steps[later]
+                ));
+            }
+        }
+    }
+
+    rng.shuffle(&mut instructions);
+
+    return instructions.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 12);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert_eq!(puzzle.part_1().to_string().len(), 12);
+            puzzle.part_2();
+        }
+    }
+}
@@ -0,0 +1,544 @@
+//! Day 7: The Sum of Its Parts. Instructions say which steps have to finish
+//! before others can begin. Part 1 is the order one worker does them in, part
+//! 2 how long several workers take when every step needs some seconds of work.
+//!
+//! The steps are a [`Graph`] that can be used on its own:
+//!
+//! ```
+//! let mut graph = day_7::Graph::new();
+//!
+//! for line in ["Step C must be finished before step A can begin.", "Step C must be finished before step F can begin."] {
+//!     let (parent, child) = day_7::parse_instruction(line).unwrap();
+//!     graph.add_node_pair(parent, child, 0);
+//! }
+//!
+//! assert_eq!(graph.get_free_nodes(), vec!['C']);
+//! assert_eq!(graph.get_sequence(), "CAF");
+//! ```
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use common::cancel;
+use common::parser::{char_where, literal, parse_line, Input, Parsed, Parser};
+use common::snapshot::Snapshot;
+use common::stepper::Simulation;
+use common::trace::{self, Event};
+use common::{parse_lines, Answer, Example, Params, ParseError, Solution};
+
+/// Instructions between shuffled steps that can never wait on each other in a cycle.
+pub mod generate;
+/// Finds the next ready step by checking every instruction, and runs part 2 a second at a time.
+pub mod reference;
+
+// part 2: every step takes this many seconds plus its position in the alphabet
+const STEP_OFFSET: usize = 60;
+const WORKERS: usize = 5;
+
+// part 2 works a second at a time, so the server keeps the offset down to something that finishes
+const MAX_STEP_OFFSET: usize = 100_000;
+
+/// The instructions, how many workers part 2 has and how long its steps take.
+pub struct Puzzle {
+    // (step that must finish first, step that depends on it)
+    instructions: Vec<(char, char)>,
+    step_offset: usize,
+    workers: usize,
+}
+
+/// The puzzle text's six steps, done by two workers with no extra seconds per step.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "steps",
+        input: "Step C must be finished before step A can begin.\n\
+                Step C must be finished before step F can begin.\n\
+                Step A must be finished before step B can begin.\n\
+                Step A must be finished before step D can begin.\n\
+                Step B must be finished before step E can begin.\n\
+                Step D must be finished before step E can begin.\n\
+                Step F must be finished before step E can begin.",
+        params: &[("workers", "2"), ("step_offset", "0")],
+        part_1: Some("CABDFE"),
+        part_2: Some("15"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            instructions: parse_lines(input, parse_instruction)?,
+            step_offset: STEP_OFFSET,
+            workers: WORKERS,
+        })
+    }
+
+    fn configure(&mut self, params: &Params) -> Result<(), String> {
+        self.step_offset = params.get("step_offset", self.step_offset)?;
+        self.workers = params.get("workers", self.workers)?;
+
+        if self.workers == 0 {
+            return Err(String::from("workers must be at least 1"));
+        }
+
+        return Ok(());
+    }
+
+    fn check_size(&self) -> Result<(), String> {
+        if self.step_offset > MAX_STEP_OFFSET {
+            return Err(format!("step_offset {} is more than the {} allowed", self.step_offset, MAX_STEP_OFFSET));
+        }
+
+        return Ok(());
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut graph = self.build_graph(0);
+
+        return Answer::Text(graph.get_sequence());
+    }
+
+    fn part_2(&self) -> Answer {
+        let mut graph = self.build_graph(self.step_offset);
+
+        return Answer::from(graph.get_completion_time(self.workers));
+    }
+
+    // part 2's workers, a second at a time
+    fn simulate(&self) -> Option<Box<dyn Simulation + '_>> {
+        let graph = self.build_graph(self.step_offset);
+
+        return Some(Box::new(Workers { graph, workers: self.workers, time: 0, done: vec![] }));
+    }
+
+    fn resume(&self, snapshot: &Snapshot) -> Result<Box<dyn Simulation + '_>, String> {
+        let mut graph = Graph::new();
+
+        for step in snapshot.get("steps")?.split_whitespace() {
+            let invalid = || format!("the snapshot's steps should look like C:3:AB, not {}", step);
+
+            let (name, rest) = step.split_once(':').ok_or_else(invalid)?;
+            let (effort, parents) = rest.split_once(':').ok_or_else(invalid)?;
+            let name = match name.chars().collect::<Vec<char>>().as_slice() {
+                [name] => *name,
+                _ => return Err(invalid()),
+            };
+
+            let effort = effort.parse().map_err(|_| invalid())?;
+            graph.nodes.insert(name, Node { parents: parents.chars().collect(), children: vec![], effort });
+        }
+
+        // children aren't saved, every step's parents say the same thing
+        let pairs: Vec<(char, char)> = graph.nodes.iter()
+            .flat_map(|(&child, node)| node.parents.iter().map(move |&parent| (parent, child)))
+            .collect();
+        for (parent, child) in pairs {
+            match graph.nodes.get_mut(&parent) {
+                Some(node) => node.children.push(child),
+                None => return Err(format!("the snapshot's step {} waits on {}, which is already done", child, parent)),
+            }
+        }
+
+        graph.in_progress = snapshot.get("in_progress")?.chars().collect();
+        if let Some(step) = graph.in_progress.iter().find(|step| !graph.nodes.contains_key(step)) {
+            return Err(format!("the snapshot has a worker on {}, which isn't one of its steps", step));
+        }
+        if graph.in_progress.len() > self.workers {
+            return Err(format!("the snapshot has {} workers busy but there are only {}", graph.in_progress.len(), self.workers));
+        }
+
+        let done = snapshot.get("done")?.chars().collect();
+
+        return Ok(Box::new(Workers { graph, workers: self.workers, time: snapshot.tick as usize, done }));
+    }
+}
+
+// for `aoc step`
+struct Workers {
+    graph: Graph,
+    workers: usize,
+    time: usize,
+    done: Vec<char>,
+}
+
+impl Simulation for Workers {
+    fn tick(&self) -> u64 {
+        return self.time as u64;
+    }
+
+    fn step(&mut self) -> bool {
+        match self.graph.reduce_effort(self.workers) {
+            Some(finished) => {
+                self.done.extend(finished);
+                self.time += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn view(&self, _width: usize) -> Vec<String> {
+        let mut lines = vec![];
+
+        for worker in 0..self.workers {
+            match self.graph.in_progress.get(worker) {
+                Some(step) => lines.push(format!("{} {}s left", step, self.graph.nodes[step].effort)),
+                None => lines.push(String::from(".")),
+            }
+        }
+
+        let waiting: String = self.graph.get_free_nodes().into_iter().filter(|step| !self.graph.in_progress.contains(step)).collect();
+        let done: String = self.done.iter().collect();
+
+        lines.push(String::new());
+        lines.push(format!("waiting: {}", waiting));
+        lines.push(format!("done: {}", done));
+
+        return lines;
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, i64)> {
+        return vec![("working", self.graph.in_progress.len() as i64), ("left", self.graph.nodes.len() as i64)];
+    }
+
+    // each step left as step:seconds:parents, like C:3:AB
+    fn snapshot(&self) -> Snapshot {
+        let mut steps: Vec<String> = self.graph.nodes.iter()
+            .map(|(step, node)| format!("{}:{}:{}", step, node.effort, node.parents.iter().collect::<String>()))
+            .collect();
+        steps.sort();
+
+        return Snapshot::new(7, self.time as u64)
+            .with_list("steps", steps)
+            .with("in_progress", self.graph.in_progress.iter().collect::<String>())
+            .with("done", self.done.iter().collect::<String>());
+    }
+}
+
+/// One instruction as (the step that must finish first, the step that waits
+/// on it), from a line like `Step C must be finished before step A can begin.`
+pub fn parse_instruction(str: &str) -> Result<(char, char), ParseError> {
+    return parse_line(str, instruction);
+}
+
+// Step C must be finished before step A can begin.
+fn instruction(input: &mut Input) -> Parsed<(char, char)> {
+    let step = || char_where("a step letter", |c| c.is_ascii_uppercase());
+
+    literal("Step ").parse(input)?;
+    let finish_first = step().parse(input)?;
+    literal(" must be finished before step ").parse(input)?;
+    let finish_second = step().parse(input)?;
+    literal(" can begin.").parse(input)?;
+
+    return Ok((finish_first, finish_second));
+}
+
+impl Puzzle {
+    fn build_graph(&self, offset: usize) -> Graph {
+        let mut graph = Graph::new();
+
+        for &(finish_first, finish_second) in self.instructions.iter() {
+            graph.add_node_pair(finish_first, finish_second, offset);
+        }
+
+        return graph;
+    }
+}
+
+/// A step and the steps around it.
+pub struct Node {
+    /// The steps that have to finish before this one can begin, sorted.
+    pub parents: Vec<char>,
+    /// The steps waiting on this one, sorted.
+    pub children: Vec<char>,
+    /// How many seconds of work are left on this step.
+    pub effort: usize
+}
+
+/// Every step that isn't done yet, and which ones workers have started on.
+/// Steps leave the graph as they finish.
+#[derive(Default)]
+pub struct Graph {
+    nodes: HashMap<char, Node>,
+    // steps a worker has started on, in the order they were picked up
+    in_progress: Vec<char>
+}
+
+impl Graph {
+    /// A graph with no steps.
+    pub fn new() -> Graph {
+        Graph { nodes: HashMap::new(), in_progress: vec![] }
+    }
+
+    /// A step that isn't done yet.
+    pub fn node(&self, step: char) -> Option<&Node> {
+        return self.nodes.get(&step);
+    }
+
+    /// The steps workers are on, in the order they were picked up.
+    pub fn in_progress(&self) -> &[char] {
+        return &self.in_progress;
+    }
+
+    /// Adds the instruction that `parent` must finish before `child` can
+    /// begin, adding either step if it's new. A new step takes `offset`
+    /// seconds plus its place in the alphabet.
+    pub fn add_node_pair(&mut self, parent: char, child: char, offset: usize) {
+        let parent_node = self.nodes.entry(parent).or_insert(Node {
+            parents: vec![],
+            children: vec![],
+            effort: get_effort(parent, offset)
+        });
+
+        // the same instruction twice doesn't say anything new
+        if parent_node.children.contains(&child) {
+            return;
+        }
+
+        parent_node.children.push(child);
+        parent_node.children.sort();
+
+        let child_node = self.nodes.entry(child).or_insert(Node {
+            parents: vec![],
+            children: vec![],
+            effort: get_effort(child, offset)
+        });
+        
+        child_node.parents.push(parent);
+        child_node.parents.sort();
+    }
+
+    /// The steps that aren't waiting on anything, in alphabetical order.
+    pub fn get_free_nodes(&self) -> Vec<char> {
+        let mut entry_points = vec![];
+
+        for (key, value) in self.nodes.iter() {
+            if value.parents.is_empty() {
+                entry_points.push(*key);
+            }
+        }
+
+        entry_points.sort();
+        return entry_points;
+    }
+
+    /// Marks `parent` as done, so nothing waits on it any more.
+    pub fn remove_parent_from_nodes(&mut self, parent: char) {
+        for (_, value) in self.nodes.iter_mut() {
+            if value.parents.contains(&parent) {
+                let index = value.parents.iter().position(|x| *x == parent).unwrap();
+                value.parents.remove(index);
+            }
+        }
+
+        self.nodes.remove(&parent);
+    }
+
+    /// Does every step one at a time, always the first free one alphabetically,
+    /// and returns the order they were done in. This empties the graph.
+    pub fn get_sequence(&mut self) -> String {
+        let mut free_nodes = self.get_free_nodes();
+        let mut final_sequence: Vec<char> = vec![];
+
+        while !free_nodes.is_empty() && !cancel::requested() {
+            self.remove_parent_from_nodes(free_nodes[0]);
+
+            final_sequence.push(free_nodes[0]);
+
+            free_nodes = self.get_free_nodes();
+
+            trace::emit(final_sequence.len() as u64, || {
+                let done: String = final_sequence.iter().collect();
+                Event::new(format!("done {}, {} free", done, free_nodes.len())).changed(final_sequence.last())
+            });
+        }
+
+        return final_sequence.into_iter().collect();
+    }
+
+    /// One second of work by up to `number_of_workers` workers, returning the
+    /// steps that finished in it or None once everything is done.
+    pub fn reduce_effort(&mut self, number_of_workers: usize) -> Option<Vec<char>> {
+        let free_nodes = self.get_free_nodes();
+        let mut finished = vec![];
+
+        if free_nodes.is_empty() {
+            return None;
+        }
+
+        // a worker sticks with its step until it is done, even if an earlier letter frees up
+        for node in free_nodes {
+            if self.in_progress.len() < number_of_workers && !self.in_progress.contains(&node) {
+                self.in_progress.push(node);
+            }
+        }
+
+        for node in self.in_progress.clone() {
+            let node_state = self.nodes.get_mut(&node).unwrap();
+            node_state.effort -= 1;
+
+            if node_state.effort == 0 {
+                self.remove_parent_from_nodes(node);
+                self.in_progress.retain(|x| *x != node);
+                finished.push(node);
+            }
+        }
+
+        return Some(finished);
+    }
+
+    /// How many seconds `workers` workers take to do every step. This empties
+    /// the graph.
+    pub fn get_completion_time(&mut self, workers: usize) -> usize {
+        let mut time = 0;
+
+        while !cancel::requested() {
+            let finished = match self.reduce_effort(workers) {
+                Some(finished) => finished,
+                None => break,
+            };
+            time += 1;
+
+            trace::emit(time as u64, || {
+                let working: String = self.in_progress.iter().collect();
+                Event::new(format!("working on {}, {} steps left", working, self.nodes.len())).changed(finished)
+            });
+        }
+
+        return time;
+    }
+}
+
+/// How many seconds step `c` takes: `offset` plus its place in the alphabet.
+pub fn get_effort(c: char, mut offset: usize) -> usize {
+    match c {
+        'a' | 'A' => { offset += 1 },
+        'b' | 'B' => { offset += 2 },
+        'c' | 'C' => { offset += 3 },
+        'd' | 'D' => { offset += 4 },
+        'e' | 'E' => { offset += 5 },
+        'f' | 'F' => { offset += 6 },
+        'g' | 'G' => { offset += 7 },
+        'h' | 'H' => { offset += 8 },
+        'i' | 'I' => { offset += 9 },
+        'j' | 'J' => { offset += 10 },
+        'k' | 'K' => { offset += 11 },
+        'l' | 'L' => { offset += 12 },
+        'm' | 'M' => { offset += 13 },
+        'n' | 'N' => { offset += 14 },
+        'o' | 'O' => { offset += 15 },
+        'p' | 'P' => { offset += 16 },
+        'q' | 'Q' => { offset += 17 },
+        'r' | 'R' => { offset += 18 },
+        's' | 'S' => { offset += 19 },
+        't' | 'T' => { offset += 20 },
+        'u' | 'U' => { offset += 21 },
+        'v' | 'V' => { offset += 22 },
+        'w' | 'W' => { offset += 23 },
+        'x' | 'X' => { offset += 24 },
+        'y' | 'Y' => { offset += 25 },
+        'z' | 'Z' => { offset += 26 },
+        _ => { offset += 0 }
+    }
+
+    return offset;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn check_size_works() {
+        let mut puzzle = Puzzle::parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(puzzle.check_size(), Ok(()));
+
+        common::configure(&mut puzzle, &Params::from_pairs(&[("step_offset", &usize::MAX.to_string())])).unwrap();
+        assert!(puzzle.check_size().is_err());
+    }
+
+    #[test]
+    fn parse_instruction_works() {
+        assert_eq!(parse_instruction("Step C must be finished before step A can begin."), Ok(('C', 'A')));
+        assert_eq!(parse_instruction("Step C must finish before A").unwrap_err().column, 7);
+    }
+
+    #[test]
+    fn get_free_nodes_works() {
+        let mut graph = Graph::new();
+        graph.add_node_pair('c', 'a', 0);
+        graph.add_node_pair('c', 'f', 0);
+        graph.add_node_pair('a', 'b', 0);
+        graph.add_node_pair('a', 'd', 0);
+        graph.add_node_pair('b', 'e', 0);
+        graph.add_node_pair('d', 'e', 0);
+        graph.add_node_pair('f', 'e', 0);
+        assert_eq!(graph.get_free_nodes(), vec!['c']);
+
+        graph.remove_parent_from_nodes('c');
+        assert_eq!(graph.get_free_nodes(), vec!['a', 'f']);
+    }
+    
+    #[test]
+    fn simulate_works() {
+        let mut puzzle = Puzzle::parse(EXAMPLES[0].input).unwrap();
+        puzzle.workers = 2;
+        puzzle.step_offset = 0;
+
+        let mut workers = puzzle.simulate().unwrap();
+        workers.step();
+        assert_eq!(workers.view(80), vec!["C 2s left", ".", "", "waiting: ", "done: "]);
+
+        let snapshot = workers.snapshot();
+        assert_eq!(snapshot.get("steps"), Ok("A:1:C B:2:A C:2: D:4:A E:5:BDF F:6:C"));
+
+        let mut resumed = puzzle.resume(&snapshot).unwrap();
+        while resumed.step() {}
+        assert_eq!(resumed.view(80).last().unwrap(), "done: CABFDE");
+
+        while workers.step() {}
+        assert_eq!(workers.tick(), 15);
+        assert_eq!(workers.view(80).last().unwrap(), "done: CABFDE");
+
+        let orphan = Snapshot::new(7, 1).with("steps", "A:1:C").with("in_progress", "").with("done", "");
+        assert!(puzzle.resume(&orphan).is_err());
+    }
+
+    #[test]
+    fn get_sequence_works() {
+        let mut graph = Graph::new();
+
+        graph.add_node_pair('c', 'a', 0);
+        graph.add_node_pair('c', 'f', 0);
+        graph.add_node_pair('a', 'b', 0);
+        graph.add_node_pair('a', 'd', 0);
+        graph.add_node_pair('b', 'e', 0);
+        graph.add_node_pair('d', 'e', 0);
+        graph.add_node_pair('f', 'e', 0);
+        graph.add_node_pair('c', 'a', 0);
+        assert_eq!(graph.node('a').unwrap().parents, vec!['c']);
+
+        assert_eq!(graph.get_sequence(), String::from("cabdfe"));
+    }
+
+    #[test]
+    fn get_completion_time_works() {
+        let mut graph = Graph::new();
+
+        graph.add_node_pair('c', 'a', 0);
+        graph.add_node_pair('c', 'f', 0);
+        graph.add_node_pair('a', 'b', 0);
+        graph.add_node_pair('a', 'd', 0);
+        graph.add_node_pair('b', 'e', 0);
+        graph.add_node_pair('d', 'e', 0);
+        graph.add_node_pair('f', 'e', 0);
+
+        assert_eq!(graph.get_completion_time(2), 15);
+    }
+}
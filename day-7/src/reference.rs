@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given up to 26 steps, one per letter.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 26 };
+
+// (step that must finish first, step that depends on it)
+fn instructions(input: &str) -> Vec<(char, char)> {
+    return input
+        .lines()
+        .map(|line| {
+            let words: Vec<&str> = line.split(' ').collect();
+            (words[1].chars().next().unwrap(), words[7].chars().next().unwrap())
+        })
+        .collect();
+}
+
+fn steps(instructions: &[(char, char)]) -> BTreeSet<char> {
+    return instructions.iter().flat_map(|&(first, second)| vec![first, second]).collect();
+}
+
+fn ready(step: char, instructions: &[(char, char)], done: &[char]) -> bool {
+    return instructions.iter().all(|&(first, second)| second != step || done.contains(&first));
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let instructions = instructions(input);
+    let mut remaining = steps(&instructions);
+    let mut done = vec![];
+
+    while let Some(&step) = remaining.iter().find(|&&step| ready(step, &instructions, &done)) {
+        remaining.remove(&step);
+        done.push(step);
+    }
+
+    return Answer::from(done.into_iter().collect::<String>());
+}
+
+// one second at a time, with each worker holding (step, seconds left)
+fn part_2(input: &str, params: &Params) -> Answer {
+    let instructions = instructions(input);
+    let workers = params.get("workers", 5_usize).unwrap();
+    let step_offset = params.get("step_offset", 60_usize).unwrap();
+
+    let mut remaining = steps(&instructions);
+    let mut working: Vec<(char, usize)> = vec![];
+    let mut done = vec![];
+    let mut time = 0;
+
+    while !remaining.is_empty() || !working.is_empty() {
+        let available: Vec<char> = remaining.iter().cloned().filter(|&step| ready(step, &instructions, &done)).collect();
+
+        for step in available {
+            if working.len() < workers {
+                remaining.remove(&step);
+                working.push((step, step_offset + (step as usize - 'A' as usize + 1)));
+            }
+        }
+
+        for work in working.iter_mut() {
+            work.1 -= 1;
+        }
+
+        done.extend(working.iter().filter(|work| work.1 == 0).map(|work| work.0));
+        working.retain(|work| work.1 > 0);
+        time += 1;
+    }
+
+    return Answer::from(time);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
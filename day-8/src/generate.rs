@@ -0,0 +1,131 @@
+use common::rng::Rng;
+
+/// Roughly how many nodes `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 1000;
+
+// the most nodes a side branch of a deep tree gets
+const SIDE_BRANCH: usize = 8;
+
+/// A license tree of about `size` nodes. Each node spends part of the budget
+/// it's given on children. About half the trees are bushy, splitting the
+/// budget at random between children. The rest hand almost all of it to the
+/// last child, so they get deeper the bigger they are.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let deep = rng.chance(0.5);
+    let numbers: Vec<String> = write_tree(rng, size.max(1) - 1, deep).iter().map(|number| number.to_string()).collect();
+
+    return numbers.join(" ");
+}
+
+// a node that's been written up to its children
+struct Open {
+    children: usize,
+    metadata: usize,
+    written: usize,
+    // the budget not yet handed to a child
+    remaining: usize,
+}
+
+/*
+ * Writes the nodes depth first the way a recursive walk would, keeping the
+ * nodes it's inside on a stack of its own since a branch can be as deep as
+ * the whole budget.
+ */
+fn write_tree(rng: &mut Rng, mut budget: usize, deep: bool) -> Vec<i64> {
+    let mut numbers = vec![];
+    let mut open: Vec<Open> = vec![];
+
+    loop {
+        let children = if budget == 0 {
+            0
+        } else if rng.chance(0.3) {
+            1
+        } else {
+            rng.range(1, 4).min(budget as i64) as usize
+        };
+
+        let metadata = rng.range(1, 3) as usize;
+        numbers.push(children as i64);
+        numbers.push(metadata as i64);
+        open.push(Open { children, metadata, written: 0, remaining: budget - children });
+
+        // pick the next child's share of the budget, finishing every node that has all its children
+        loop {
+            let node = match open.last_mut() {
+                Some(node) => node,
+                None => return numbers,
+            };
+
+            if node.written < node.children {
+                budget = if node.written == node.children - 1 {
+                    node.remaining
+                } else if deep {
+                    rng.below(node.remaining.min(SIDE_BRANCH) as u64 + 1) as usize
+                } else {
+                    rng.below(node.remaining as u64 + 1) as usize
+                };
+                node.remaining -= budget;
+                node.written += 1;
+                break;
+            }
+
+            // metadata doubles as a 1 based child index in part 2, so keep some of them in range
+            for _i in 0..node.metadata {
+                numbers.push(rng.range(1, node.children as i64 + 3));
+            }
+
+            open.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 60);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            let headers = input.split(' ').count();
+            assert!(headers > 60 * 2);
+            puzzle.part_1();
+            puzzle.part_2();
+        }
+    }
+
+    // how many nodes deep the tree goes
+    fn depth(input: &str) -> usize {
+        let numbers: Vec<usize> = input.split(' ').map(|number| number.parse().unwrap()).collect();
+        let (mut open, mut deepest, mut index) = (vec![], 0, 0);
+
+        while index < numbers.len() {
+            open.push((numbers[index], numbers[index + 1]));
+            deepest = deepest.max(open.len());
+            index += 2;
+
+            while let Some(&(0, metadata)) = open.last() {
+                open.pop();
+                index += metadata;
+
+                if let Some(parent) = open.last_mut() {
+                    parent.0 -= 1;
+                }
+            }
+        }
+
+        return deepest;
+    }
+
+    #[test]
+    fn deep_trees_solve() {
+        let input = (0..10).map(|seed| generate(&mut Rng::new(seed), 200_000)).find(|input| depth(input) > 20_000).unwrap();
+        let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+        puzzle.part_1();
+        puzzle.part_2();
+    }
+}
@@ -0,0 +1,385 @@
+//! Day 8: Memory Maneuver. The license file is a tree of [`Node`]s written
+//! out as a flat list of numbers. Part 1 adds up every node's metadata, part 2
+//! works out the root's value, where a node with children uses its metadata to
+//! pick which children count.
+//!
+//! ```
+//! let root = day_8::Node::from_list(&[2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2]).unwrap();
+//!
+//! assert_eq!(root.children.len(), 2);
+//! assert_eq!(day_8::calc_metadata(&root), 138);
+//! assert_eq!(day_8::calc_metadata_v2(&root), 66);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::mem;
+
+use common::parser::{literal, many1, parse_line, separated, spanned, unsigned, whitespace, Input, Parsed, Parser};
+use common::{Answer, Example, ParseError, Solution};
+
+/// License trees, about half of them bushy and the rest deep.
+pub mod generate;
+/// Reads the tree recursively, the way the puzzle text describes it.
+pub mod reference;
+
+/// The license tree.
+pub struct Puzzle {
+    root: Node,
+}
+
+/// The puzzle text's tree of four nodes.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "license",
+        input: "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2",
+        params: &[],
+        part_1: Some("138"),
+        part_2: Some("66"),
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let line = input.trim_end();
+        let spans = parse_line(line, numbers)?;
+        let numbers: Vec<usize> = spans.iter().map(|&(_column, number)| number).collect();
+
+        // get_node_from_list slices blindly, so make sure the headers describe exactly this list
+        match tree_length(&numbers, 0) {
+            Some(length) if length == numbers.len() => {}
+            Some(length) => {
+                let column = spans[length].0;
+                return Err(ParseError::new("the end of the tree", line, column));
+            }
+            None => {
+                let expected = "more numbers, the headers describe a bigger tree";
+                return Err(ParseError::new(expected, line, line.len() + 1));
+            }
+        }
+
+        Ok(Puzzle { root: get_node_from_list(numbers.as_slice()) })
+    }
+
+    fn part_1(&self) -> Answer {
+        return Answer::from(calc_metadata(&self.root));
+    }
+
+    fn part_2(&self) -> Answer {
+        return Answer::from(calc_metadata_v2(&self.root));
+    }
+}
+
+// every space separated number along with the 1 based column it starts at
+fn numbers(input: &mut Input) -> Parsed<Vec<(usize, usize)>> {
+    whitespace().parse(input)?;
+    return separated(spanned(unsigned()), many1(literal(" "))).parse(input);
+}
+
+/// Where the node starting at `start` ends, or None if the list runs out first.
+pub fn tree_length(list: &[usize], start: usize) -> Option<usize> {
+    // (children still to read, metadata) for every node that's been started but not finished
+    let mut open: Vec<(usize, usize)> = vec![];
+    let mut end = start;
+
+    loop {
+        open.push((*list.get(end)?, *list.get(end.checked_add(1)?)?));
+        end += 2;
+
+        // a node's metadata comes straight after its last child
+        while let Some(&(0, num_of_metadata)) = open.last() {
+            open.pop();
+            end = end.checked_add(num_of_metadata).filter(|&end| end <= list.len())?;
+
+            match open.last_mut() {
+                Some(parent) => parent.0 -= 1,
+                None => return Some(end),
+            }
+        }
+    }
+}
+
+/// Every piece of metadata in the tree under `node`, added up.
+pub fn calc_metadata(node: &Node) -> usize {
+    let mut count = 0;
+    let mut nodes = vec![node];
+
+    while let Some(node) = nodes.pop() {
+        count += node.metadata.iter().sum::<usize>();
+        nodes.extend(node.children.iter());
+    }
+
+    return count;
+}
+
+/// The value of `node`: its metadata added up when it has no children,
+/// otherwise the values of the children its metadata points at (1 for the
+/// first child), skipping any that point past the last one.
+pub fn calc_metadata_v2(node: &Node) -> usize {
+    let mut count = 0;
+    // every node whose value counts, and how many times it counts
+    let mut nodes = vec![(node, 1)];
+
+    while let Some((node, times)) = nodes.pop() {
+        if node.children.is_empty() {
+            count += times * node.metadata.iter().sum::<usize>();
+            continue;
+        }
+
+        // each child is only visited once however many entries point at it
+        let mut pointed_at = vec![0; node.children.len()];
+        for &entry in node.metadata.iter() {
+            if entry <= node.children.len() && entry > 0 {
+                pointed_at[entry - 1] += 1;
+            }
+        }
+
+        for (child, pointers) in node.children.iter().zip(pointed_at) {
+            if pointers > 0 {
+                nodes.push((child, times * pointers));
+            }
+        }
+    }
+
+    return count;
+}
+
+fn get_node_from_list(list: &[usize]) -> Node {
+    // nodes whose children are still being read, the innermost last
+    let mut open: Vec<Node> = vec![];
+    let mut index = 0;
+
+    loop {
+        let header = (list[index], list[index + 1]);
+        index += 2;
+        open.push(Node { header, children: vec![], metadata: vec![] });
+
+        while open.last().is_some_and(|node| node.children.len() == node.header.0) {
+            let mut node = open.pop().unwrap();
+            node.metadata = list[index..index + node.header.1].to_vec();
+            index += node.header.1;
+
+            match open.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => return node,
+            }
+        }
+    }
+}
+
+/// A node of the license tree.
+#[derive(Debug)]
+pub struct Node {
+    /// How many children and pieces of metadata the node has, as written.
+    pub header: (usize, usize),
+    /// The node's metadata entries.
+    pub metadata: Vec<usize>,
+    /// The node's children, in order.
+    pub children: Vec<Node>,
+}
+
+// dropping a node drops its children first, once per level, which a deep enough tree overflows
+impl Drop for Node {
+    fn drop(&mut self) {
+        let mut children = mem::take(&mut self.children);
+
+        while let Some(mut child) = children.pop() {
+            children.append(&mut child.children);
+        }
+    }
+}
+
+impl Node {
+    /// The tree the list describes, or None when its headers don't add up to
+    /// exactly the numbers given.
+    pub fn from_list(list: &[usize]) -> Option<Node> {
+        if tree_length(list, 0)? != list.len() {
+            return None;
+        }
+
+        return Some(get_node_from_list(list));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Puzzle::parse("0 1 x").err().unwrap().column, 5);
+        assert_eq!(Puzzle::parse("1 1 0 2 7").err().unwrap().column, 10);
+        assert_eq!(Puzzle::parse("0 1 7  8").err().unwrap().column, 8);
+        assert!(Puzzle::parse("1 1 0 1 7 8\n").is_ok());
+    }
+
+    #[test]
+    fn example_works() {
+        let example = &[2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2];
+        let node = get_node_from_list(example);
+
+        assert_eq!(node.header, (2, 3));
+        assert_eq!(node.metadata, [1, 1, 2]);
+
+        assert_eq!(node.children[0].header, (0, 3));
+        assert_eq!(node.children[0].metadata, [10, 11, 12]);
+
+        assert_eq!(node.children[1].header, (1, 1));
+        assert_eq!(node.children[1].metadata, [2]);
+
+        assert_eq!(node.children[1].children[0].header, (0, 1));
+        assert_eq!(node.children[1].children[0].metadata, [99]);
+
+        assert_eq!(calc_metadata(&node), 138);
+        assert_eq!(calc_metadata_v2(&node), 66);
+    }
+
+    #[test]
+    fn from_list_works() {
+        assert_eq!(Node::from_list(&[1, 1, 0, 1, 7, 8]).map(|node| calc_metadata(&node)), Some(15));
+        assert!(Node::from_list(&[1, 1, 0, 2, 7]).is_none());
+        assert!(Node::from_list(&[0, 1, 7, 8]).is_none());
+        assert!(Node::from_list(&[]).is_none());
+    }
+
+    #[test]
+    fn deep_trees_work() {
+        // a chain of nodes with one child each, every one pointing at its child
+        let depth = 200_000;
+        let mut list = [1, 1].repeat(depth);
+        list.extend([0, 1, 7]);
+        list.extend(vec![1; depth]);
+
+        let root = Node::from_list(&list).unwrap();
+        assert_eq!((calc_metadata(&root), calc_metadata_v2(&root)), (depth + 7, 7));
+
+        list.pop();
+        assert!(Node::from_list(&list).is_none());
+        assert_eq!(tree_length(&[0, usize::MAX], 0), None);
+    }
+
+    #[test]
+    fn multiple_children_with_works() {
+        let example = &[2, 1, 0, 1, 7, 0, 1, 8, 9];
+        let node = get_node_from_list(example);
+
+        assert_eq!(node.header, (2, 1));
+        assert_eq!(node.metadata, [9]);
+        assert_eq!(node.children.len(), 2);
+
+        let child = &node.children[0];
+        assert_eq!(child.header, (0, 1));
+        assert_eq!(child.metadata, [7]);
+        assert_eq!(child.children.len(), 0);
+
+        let child = &node.children[1];
+        assert_eq!(child.header, (0, 1));
+        assert_eq!(child.metadata, [8]);
+        assert_eq!(child.children.len(), 0);
+
+        assert_eq!(calc_metadata(&node), 24);
+    }
+
+    #[test]
+    fn base_case_works() {
+        let example = &[0, 0];
+        let node = get_node_from_list(example);
+        assert_eq!(node.header, (0, 0));
+        assert_eq!(node.metadata.len(), 0);
+        assert_eq!(node.children.len(), 0);
+
+        assert_eq!(calc_metadata(&node), 0);
+        
+        let example = &[0, 2, 4, 5];
+        let node = get_node_from_list(example);
+        assert_eq!(node.header, (0, 2));
+        assert_eq!(node.metadata, [4, 5]);
+        assert_eq!(node.children.len(), 0);
+
+        assert_eq!(calc_metadata(&node), 9);
+        assert_eq!(calc_metadata_v2(&node), 9);
+    }
+
+    #[test]
+    fn one_layer_deep_works() {
+        let example = &[1, 1, 0, 1, 7, 8];
+        let node = get_node_from_list(example);
+
+        assert_eq!(node.header, (1, 1));
+        assert_eq!(node.metadata, [8]);
+        assert_eq!(node.children.len(), 1);
+
+        let child = &node.children[0];
+        assert_eq!(child.header, (0, 1));
+        assert_eq!(child.metadata, [7]);
+        assert_eq!(child.children.len(), 0);
+    }
+
+    #[test]
+    fn no_metadata_works() {
+        let example = &[1, 1, 0, 0, 1];
+        let node = get_node_from_list(example);
+
+        assert_eq!(node.header, (1, 1));
+        assert_eq!(node.metadata, [1]);
+        assert_eq!(node.children.len(), 1);
+
+        let child = &node.children[0];
+        assert_eq!(child.header, (0, 0));
+        assert_eq!(child.metadata, []);
+        assert_eq!(child.children.len(), 0);
+
+        assert_eq!(calc_metadata(&node), 1);
+    }
+
+    #[test]
+    fn n_layers_works() {
+        /*
+        2  1  1  1  0  1  6  7  0  1  8  9
+        A---------------------------------
+              B---------------- D------ 
+                    C------
+
+        */
+        let example = &[2, 1, 1, 1, 0, 1, 6, 7, 0, 1, 8, 9];
+        let node = get_node_from_list(example);
+
+        assert_eq!(node.header, (2, 1));
+        assert_eq!(node.metadata, [9]);
+        assert_eq!(node.children.len(), 2);
+
+        let child = &node.children[0];
+        assert_eq!(child.header, (1, 1));
+        assert_eq!(child.metadata, [7]);
+        assert_eq!(child.children.len(), 1);
+
+        let child = &node.children[0].children[0];
+        assert_eq!(child.header, (0, 1));
+        assert_eq!(child.metadata, [6]);
+        assert_eq!(child.children.len(), 0);
+
+        let child = &node.children[1];
+        assert_eq!(child.header, (0, 1));
+        assert_eq!(child.metadata, [8]);
+        assert_eq!(child.children.len(), 0);
+
+        assert_eq!(calc_metadata(&node), 30);
+        assert_eq!(calc_metadata_v2(&node), 0);
+    }
+
+    #[test]
+    fn v2_works() {
+        let example = &[2, 1, 1, 1, 0, 1, 1, 1, 0, 1, 8, 1];
+        let node = get_node_from_list(example);
+        assert_eq!(calc_metadata_v2(&node), 1);
+
+        let example = &[1, 1, 0, 1, 7, 1];
+        let node = get_node_from_list(example);
+        assert_eq!(calc_metadata_v2(&node), 7);
+    }
+}
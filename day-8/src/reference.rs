@@ -0,0 +1,60 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given trees of about 500 nodes, which are shallow enough to recurse through.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 500 };
+
+fn numbers(input: &str) -> Vec<usize> {
+    return input.split_whitespace().map(|number| number.parse().unwrap()).collect();
+}
+
+// reads the node at the cursor and moves it past the node, returning (part 1, part 2) for it
+fn node(numbers: &[usize], cursor: &mut usize) -> (usize, usize) {
+    let children = numbers[*cursor];
+    let metadata = numbers[*cursor + 1];
+    *cursor += 2;
+
+    let mut sum = 0;
+    let mut values = vec![];
+
+    for _child in 0..children {
+        let (child_sum, child_value) = node(numbers, cursor);
+        sum += child_sum;
+        values.push(child_value);
+    }
+
+    let entries = &numbers[*cursor..*cursor + metadata];
+    *cursor += metadata;
+    sum += entries.iter().sum::<usize>();
+
+    let value = match children {
+        0 => entries.iter().sum(),
+        _ => entries.iter().filter(|&&entry| entry >= 1 && entry <= children).map(|&entry| values[entry - 1]).sum(),
+    };
+
+    return (sum, value);
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    return Answer::from(node(&numbers(input), &mut 0).0);
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    return Answer::from(node(&numbers(input), &mut 0).1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
@@ -0,0 +1,26 @@
+use common::rng::Rng;
+
+/// What the last marble is worth in a game `aoc generate` makes without a `--size`.
+pub const DEFAULT_SIZE: usize = 100_000;
+
+/// A game whose last marble is worth `size` points, with a random number of players.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    return format!("{} players; last marble is worth {} points", rng.range(2, 500), size.max(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 500);
+            let puzzle = crate::Puzzle::parse(&input).unwrap();
+
+            assert!(input.ends_with("worth 500 points"));
+            puzzle.part_1();
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Day 9: Marble Mania. Elves take turns placing numbered marbles in a
+//! circle, and every 23rd marble scores instead. Both parts want the winning
+//! score, part 2 for a game a hundred times longer.
+//!
+//! ```
+//! use std::collections::VecDeque;
+//! use day_9::Circle;
+//!
+//! // the current marble is at the back
+//! let mut circle = VecDeque::from(vec![1, 2, 3, 4]);
+//! circle.clockwise(1);
+//! assert_eq!(circle, VecDeque::from(vec![4, 1, 2, 3]));
+//!
+//! assert_eq!(day_9::play_game(9, 25), 32);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::collections::VecDeque;
+
+use common::cancel;
+use common::parser::{literal, parse_line, unsigned, Input, Parsed, Parser};
+use common::snapshot::Snapshot;
+use common::stepper::{self, Simulation};
+use common::trace::{self, Event};
+use common::{Answer, Example, Params, ParseError, Solution};
+
+/// Games with a random number of players.
+pub mod generate;
+/// Plays each game with the circle in a plain vector, inserting and removing marbles in place.
+pub mod reference;
+
+// Took ideas from: https://gist.github.com/tpoliaw/fcf797ea99ed183635717f02258f1c9b
+
+/// A ring of items kept in a line, with the current one at the back so
+/// clockwise from it is the front.
+pub trait Circle<T> {
+    /// Moves the current position `i` items clockwise.
+    fn clockwise(&mut self, i: usize);
+    /// Moves the current position `i` items counter clockwise.
+    fn counter_clockwise(&mut self, i: usize);
+}
+
+impl<T> Circle<T> for VecDeque<T> {
+    fn clockwise(&mut self, i : usize) {
+        for _ in 0..i {
+            if let Some(val) = self.pop_back() {
+                self.push_front(val);
+            }
+        }
+    }
+
+    fn counter_clockwise(&mut self, i : usize) {
+        for _ in 0..i {
+            if let Some(val) = self.pop_front() {
+                self.push_back(val);
+            }
+        }
+    }
+}
+
+// part 2 plays this many times as many marbles as the input says
+const PART_2_FACTOR: usize = 100;
+
+// the most players and marbles the server will take, part 2's circle of ten million marbles
+// is already 80MB and the real input only has 424 players
+const MAX_PLAYERS: usize = 100_000;
+const MAX_MARBLES: usize = 100_000;
+
+// the circle with the current marble at the back, so clockwise from it is the front
+struct Game {
+    circle: VecDeque<usize>,
+    scores: Vec<usize>,
+    turn: usize,
+}
+
+impl Game {
+    fn new(total_players: usize) -> Game {
+        // manually put the first marble onto the circle
+        return Game { circle: VecDeque::from(vec![0]), scores: vec![0; total_players], turn: 0 };
+    }
+
+    fn play_turn(&mut self) {
+        self.turn += 1;
+
+        let turn = self.turn;
+        let total_players = self.scores.len();
+
+        if turn.is_multiple_of(23) {
+            self.circle.clockwise(7);
+
+            self.scores[turn % total_players] += turn;
+
+            let removed = self.circle.pop_back();
+            if let Some(val) = removed {
+                self.scores[turn % total_players] += val;
+            }
+
+            self.circle.counter_clockwise(1);
+
+            trace::emit(turn as u64, || {
+                let summary = format!("elf {} scores, {} points, {} marbles", elf(turn, total_players), self.scores[turn % total_players], self.circle.len());
+                Event::new(summary).changed(removed.map(|val| format!("-{}", val)))
+            });
+        } else {
+            self.circle.counter_clockwise(1);
+            self.circle.push_back(turn);
+
+            trace::emit(turn as u64, || {
+                let summary = format!("elf {} places {}, {} marbles", elf(turn, total_players), turn, self.circle.len());
+                Event::new(summary).changed(vec![format!("+{}", turn)])
+            });
+        }
+    }
+
+    fn high_score(&self) -> usize {
+        return *self.scores.iter().max().unwrap_or(&0);
+    }
+}
+
+/// The winning score once the marble worth `total_marbles` points is played.
+/// A game the runner cancels stops early with the score so far.
+pub fn play_game(total_players: usize, total_marbles: usize) -> usize {
+    let mut game = Game::new(total_players);
+
+    while game.turn < total_marbles {
+        // a scoring turn is a good moment to check whether the runner has given up on us
+        if game.turn.is_multiple_of(23) && cancel::requested() {
+            break;
+        }
+
+        game.play_turn();
+    }
+
+    return game.high_score();
+}
+
+// how many of the highest scores `aoc step` shows
+const LEADERS: usize = 3;
+
+// the game a turn at a time, for `aoc step`
+struct Marbles {
+    game: Game,
+    total_marbles: usize,
+}
+
+impl Simulation for Marbles {
+    fn tick(&self) -> u64 {
+        return self.game.turn as u64;
+    }
+
+    fn step(&mut self) -> bool {
+        if self.game.turn >= self.total_marbles {
+            return false;
+        }
+
+        self.game.play_turn();
+        return true;
+    }
+
+    // as many marbles either side of the current one as fit, clockwise to the right
+    fn view(&self, width: usize) -> Vec<String> {
+        let circle = &self.game.circle;
+        let cell = circle.iter().max().unwrap_or(&0).to_string().len() + 1;
+        let either_side = (width / cell).saturating_sub(1) / 2;
+
+        let before = either_side.min(circle.len() - 1);
+        let after = either_side.min(circle.len() - 1 - before);
+
+        let mut marbles: Vec<String> = circle.iter().rev().skip(1).take(before).rev().map(|marble| marble.to_string()).collect();
+        marbles.push(stepper::highlight(&circle.back().unwrap().to_string()));
+        marbles.extend(circle.iter().take(after).map(|marble| marble.to_string()));
+
+        // there can be hundreds of elves, so only the best few
+        let players = self.game.scores.len();
+        let mut leaders: Vec<(usize, usize)> = (1..=players)
+            .map(|elf| (self.game.scores[elf % players], elf))
+            .filter(|&(score, _)| score > 0)
+            .collect();
+        leaders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let leaders: Vec<String> = leaders.iter().take(LEADERS).map(|(score, elf)| format!("elf {} has {}", elf, score)).collect();
+        let leaders = if leaders.is_empty() { String::from("nobody has scored yet") } else { leaders.join(", ") };
+
+        return vec![marbles.join(" "), String::new(), leaders];
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, i64)> {
+        return vec![("marbles", self.game.circle.len() as i64), ("high_score", self.game.high_score() as i64)];
+    }
+
+    // the circle from the front, so the current marble is last
+    fn snapshot(&self) -> Snapshot {
+        return Snapshot::new(9, self.game.turn as u64)
+            .with_list("scores", self.game.scores.iter())
+            .with_list("circle", self.game.circle.iter());
+    }
+}
+
+// elves take turns from the first, so the one playing `turn`
+fn elf(turn: usize, total_players: usize) -> usize {
+    return (turn - 1) % total_players + 1;
+}
+
+/// How many elves play and what the last marble is worth.
+pub struct Puzzle {
+    total_players: usize,
+    total_marbles: usize,
+}
+
+/// The puzzle text's six games.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "9 players, 25 points",
+        input: "9 players; last marble is worth 25 points",
+        params: &[],
+        part_1: Some("32"),
+        part_2: None,
+    },
+    Example {
+        name: "10 players, 1618 points",
+        input: "10 players; last marble is worth 1618 points",
+        params: &[],
+        part_1: Some("8317"),
+        part_2: None,
+    },
+    Example {
+        name: "13 players, 7999 points",
+        input: "13 players; last marble is worth 7999 points",
+        params: &[],
+        part_1: Some("146373"),
+        part_2: None,
+    },
+    Example {
+        name: "17 players, 1104 points",
+        input: "17 players; last marble is worth 1104 points",
+        params: &[],
+        part_1: Some("2764"),
+        part_2: None,
+    },
+    Example {
+        name: "21 players, 6111 points",
+        input: "21 players; last marble is worth 6111 points",
+        params: &[],
+        part_1: Some("54718"),
+        part_2: None,
+    },
+    Example {
+        name: "30 players, 5807 points",
+        input: "30 players; last marble is worth 5807 points",
+        params: &[],
+        part_1: Some("37305"),
+        part_2: None,
+    },
+];
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let (total_players, total_marbles) = parse_game(input.trim())?;
+
+        Ok(Puzzle { total_players, total_marbles })
+    }
+
+    // lets a config stand in for the sentence, part 2 still plays a hundred times as many marbles
+    fn configure(&mut self, params: &Params) -> Result<(), String> {
+        self.total_players = params.get("players", self.total_players)?;
+        self.total_marbles = params.get("marbles", self.total_marbles)?;
+
+        if self.total_players == 0 {
+            return Err(String::from("players must be at least 1"));
+        }
+
+        return Ok(());
+    }
+
+    fn check_size(&self) -> Result<(), String> {
+        if self.total_players > MAX_PLAYERS {
+            return Err(format!("{} players is more than the {} allowed", self.total_players, MAX_PLAYERS));
+        }
+        if self.total_marbles > MAX_MARBLES {
+            return Err(format!("{} marbles is more than the {} allowed", self.total_marbles, MAX_MARBLES));
+        }
+
+        return Ok(());
+    }
+
+    fn part_1(&self) -> Answer {
+        return Answer::from(play_game(self.total_players, self.total_marbles));
+    }
+
+    fn part_2(&self) -> Answer {
+        match self.total_marbles.checked_mul(PART_2_FACTOR) {
+            Some(total_marbles) => Answer::from(play_game(self.total_players, total_marbles)),
+            None => Answer::Unsolved(format!("{} times {} marbles is too many to count", PART_2_FACTOR, self.total_marbles)),
+        }
+    }
+
+    // as long a game as part 2's
+    fn simulate(&self) -> Option<Box<dyn Simulation + '_>> {
+        return Some(Box::new(Marbles { game: Game::new(self.total_players), total_marbles: self.total_marbles.saturating_mul(PART_2_FACTOR) }));
+    }
+
+    fn resume(&self, snapshot: &Snapshot) -> Result<Box<dyn Simulation + '_>, String> {
+        let scores: Vec<usize> = snapshot.get_list("scores")?;
+        let circle: VecDeque<usize> = snapshot.get_list("circle")?.into();
+
+        if scores.len() != self.total_players {
+            return Err(format!("the snapshot is of a game with {} players, not {}", scores.len(), self.total_players));
+        }
+        // every marble played is still in the circle unless it scored, along with the two it took each time
+        let turn = snapshot.tick as usize;
+        if circle.len() != turn + 1 - turn / 23 * 2 {
+            return Err(format!("the snapshot's circle should have {} marbles by turn {}", turn + 1 - turn / 23 * 2, turn));
+        }
+
+        return Ok(Box::new(Marbles { game: Game { circle, scores, turn }, total_marbles: self.total_marbles.saturating_mul(PART_2_FACTOR) }));
+    }
+}
+
+/// The number of players and the last marble's points from the input's single
+/// sentence:
+///   424 players; last marble is worth 71482 points
+pub fn parse_game(input: &str) -> Result<(usize, usize), ParseError> {
+    return parse_line(input, game);
+}
+
+fn game(input: &mut Input) -> Parsed<(usize, usize)> {
+    let players = unsigned().parse(input)?;
+    literal(" players; last marble is worth ").parse(input)?;
+    let points = unsigned().parse(input)?;
+    literal(" points").parse(input)?;
+
+    return Ok((players, points));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_work() {
+        common::example::assert_examples::<Puzzle>(EXAMPLES);
+    }
+
+    #[test]
+    fn parse_game_works() {
+        assert_eq!(parse_game("424 players; last marble is worth 71482 points"), Ok((424, 71482)));
+        assert_eq!(parse_game("424 players; last marble is worth many points").unwrap_err().column, 35);
+        assert_eq!(parse_game("424 players").unwrap_err().column, 4);
+    }
+
+    #[test]
+    fn simulate_works() {
+        let puzzle = Puzzle::parse("9 players; last marble is worth 25 points").unwrap();
+        let mut marbles = puzzle.simulate().unwrap();
+
+        for _ in 0..25 {
+            marbles.step();
+        }
+
+        // the example's last row, cut down to fit around the current marble 25
+        assert_eq!(marbles.view(24)[0], format!("2 24 20 {} 10 21 5", stepper::highlight("25")));
+        assert_eq!(marbles.metrics(), vec![("marbles", 24), ("high_score", 32)]);
+        assert_eq!(marbles.view(24)[2], "elf 5 has 32");
+
+        let mut resumed = puzzle.resume(&marbles.snapshot()).unwrap();
+        for _ in 0..100 {
+            marbles.step();
+            resumed.step();
+        }
+        assert_eq!((resumed.tick(), resumed.metrics()), (marbles.tick(), marbles.metrics()));
+
+        let other = Puzzle::parse("10 players; last marble is worth 25 points").unwrap();
+        assert!(other.resume(&marbles.snapshot()).is_err());
+    }
+
+    #[test]
+    fn check_size_works() {
+        let mut puzzle = Puzzle::parse("424 players; last marble is worth 71482 points").unwrap();
+        assert_eq!(puzzle.check_size(), Ok(()));
+
+        common::configure(&mut puzzle, &Params::from_pairs(&[("marbles", "1000000")])).unwrap();
+        assert!(puzzle.check_size().is_err());
+        assert!(Puzzle::parse("1000000 players; last marble is worth 25 points").unwrap().check_size().is_err());
+
+        // too many for part 2 to even count, let alone play
+        let puzzle = Puzzle::parse(&format!("9 players; last marble is worth {} points", usize::MAX)).unwrap();
+        assert!(matches!(puzzle.part_2(), Answer::Unsolved(_)));
+    }
+
+    #[test]
+    fn examples_works() {
+        assert_eq!(play_game(13, 7999), 146373);
+        assert_eq!(play_game(17, 1104), 2764);
+        assert_eq!(play_game(21, 6111), 54718);
+        assert_eq!(play_game(30, 5807), 37305);
+
+        // Part 1
+        assert_eq!(play_game(424, 71482), 408679);
+
+        // Part 2
+        assert_eq!(play_game(424, 7148200), 3443939356);
+    }
+}
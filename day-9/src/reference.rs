@@ -0,0 +1,56 @@
+use common::differential::Reference;
+use common::{Answer, Params};
+
+/// Only given games up to a last marble of 300, since part 2 plays a hundred times as many.
+pub const REFERENCE: Reference = Reference { part_1, part_2, params: &[], max_size: 300 };
+
+// (players, last marble)
+fn game(input: &str) -> (usize, usize) {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    return (words[0].parse().unwrap(), words[6].parse().unwrap());
+}
+
+// the circle as a plain vector, with `current` the index of the current marble
+fn high_score(players: usize, last_marble: usize) -> usize {
+    let mut circle = vec![0];
+    let mut current = 0;
+    let mut scores = vec![0; players];
+
+    for marble in 1..=last_marble {
+        if marble % 23 == 0 {
+            current = (current + circle.len() - 7) % circle.len();
+            scores[marble % players] += marble + circle.remove(current);
+        } else {
+            current = (current + 1) % circle.len() + 1;
+            circle.insert(current, marble);
+        }
+    }
+
+    return scores.into_iter().max().unwrap();
+}
+
+fn part_1(input: &str, _params: &Params) -> Answer {
+    let (players, last_marble) = game(input);
+    return Answer::from(high_score(players, last_marble));
+}
+
+fn part_2(input: &str, _params: &Params) -> Answer {
+    let (players, last_marble) = game(input);
+    return Answer::from(high_score(players, last_marble * 100));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential;
+
+    #[test]
+    fn reference_solves_examples() {
+        differential::assert_reference_examples(&REFERENCE, crate::EXAMPLES);
+    }
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        differential::assert_agrees::<crate::Puzzle>(&REFERENCE, crate::generate::generate);
+    }
+}
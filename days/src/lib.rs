@@ -0,0 +1,77 @@
+use common::rng::Rng;
+use common::differential::Reference;
+use common::{Example, ParseError, Solution};
+
+/*
+ * Every day there is, for the runner, the server and the C library alike, so
+ * a new day only has to be added here for all of them to have it.
+ */
+
+pub type Parse = fn(&str) -> Result<Box<dyn Solution>, ParseError>;
+
+pub struct Day {
+    pub number: u8,
+    pub parse: Parse,
+    pub examples: &'static [Example],
+    // writes a random but valid input of roughly the given size
+    pub generate: fn(&mut Rng, usize) -> String,
+    pub default_size: usize,
+    // a brute force solver to check the real one against on generated inputs
+    pub reference: &'static Reference,
+}
+
+impl Day {
+    // inputs live next to each day's solution, relative to the workspace root
+    pub fn input_path(&self) -> String {
+        return format!("day-{}/src/input.txt", self.number);
+    }
+}
+
+// every day crate exposes the same items, so its entry only differs by name
+macro_rules! day {
+    ($number:expr, $krate:ident) => {
+        Day {
+            number: $number,
+            parse: common::boxed::<$krate::Puzzle>,
+            examples: $krate::EXAMPLES,
+            generate: $krate::generate::generate,
+            default_size: $krate::generate::DEFAULT_SIZE,
+            reference: &$krate::reference::REFERENCE,
+        }
+    };
+}
+
+pub fn all() -> Vec<Day> {
+    return vec![
+        day!(1, day_1),
+        day!(2, day_2),
+        day!(3, day_3),
+        day!(4, day_4),
+        day!(5, day_5),
+        day!(6, day_6),
+        day!(7, day_7),
+        day!(8, day_8),
+        day!(9, day_9),
+        day!(10, day_10),
+        day!(11, day_11),
+        day!(12, day_12),
+    ];
+}
+
+pub fn find(number: u8) -> Option<Day> {
+    return all().into_iter().find(|day| day.number == number);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_works() {
+        let numbers: Vec<u8> = all().iter().map(|day| day.number).collect();
+        assert_eq!(numbers, (1..=12).collect::<Vec<u8>>());
+
+        assert_eq!(find(7).map(|day| day.input_path()), Some(String::from("day-7/src/input.txt")));
+        assert!(find(13).is_none());
+    }
+}
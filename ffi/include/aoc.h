@@ -0,0 +1,73 @@
+/*
+ * The day solvers from libaoc_ffi. Build it with `cargo build -p aoc-ffi` and
+ * link with `-laoc_ffi`.
+ *
+ * aoc_solve takes a day's number and its puzzle input as `length` bytes of
+ * UTF-8, which needn't be NUL-terminated, and hands back both answers. Each
+ * aoc_day_N does the same for its own day. Whatever comes back belongs to the
+ * caller until it's passed to aoc_answers_free:
+ *
+ *     aoc_answers answers = aoc_solve(8, input, strlen(input));
+ *     if (answers.error) fprintf(stderr, "%s\n", answers.error);
+ *     if (answers.part_1) printf("%s\n", answers.part_1);
+ *     aoc_answers_free(answers);
+ *
+ * Each part gets a minute, or whatever aoc_set_timeout last set. Most days
+ * notice when their time is up and stop, the rest finish first and still come
+ * back as a timeout.
+ *
+ * Inputs are trusted, this is not a sandbox. A panic in a solver comes back
+ * as an error, but a stack overflow or running out of memory aborts the
+ * whole process, and that can't be caught.
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * A part with an answer has it as a string, with a bitmap's rows separated by
+ * newlines. A part without one is NULL, and error says why. error is NULL when
+ * both parts were answered.
+ */
+typedef struct aoc_answers {
+    char *part_1;
+    char *part_2;
+    char *error;
+} aoc_answers;
+
+/* A day that doesn't exist comes back with only an error. */
+aoc_answers aoc_solve(unsigned char day, const char *input, size_t length);
+
+aoc_answers aoc_day_1(const char *input, size_t length);
+aoc_answers aoc_day_2(const char *input, size_t length);
+aoc_answers aoc_day_3(const char *input, size_t length);
+aoc_answers aoc_day_4(const char *input, size_t length);
+aoc_answers aoc_day_5(const char *input, size_t length);
+aoc_answers aoc_day_6(const char *input, size_t length);
+aoc_answers aoc_day_7(const char *input, size_t length);
+aoc_answers aoc_day_8(const char *input, size_t length);
+aoc_answers aoc_day_9(const char *input, size_t length);
+aoc_answers aoc_day_10(const char *input, size_t length);
+aoc_answers aoc_day_11(const char *input, size_t length);
+aoc_answers aoc_day_12(const char *input, size_t length);
+
+/*
+ * How long each part may take from now on, for every thread. Returns 0, or -1
+ * without changing anything when seconds isn't above 0.
+ */
+int aoc_set_timeout(double seconds);
+
+/* Frees every string in answers, skipping NULL ones. */
+void aoc_answers_free(aoc_answers answers);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
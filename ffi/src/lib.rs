@@ -0,0 +1,258 @@
+//! The day solvers as a C library. [`aoc_solve`] takes a day's number and its
+//! puzzle input as a buffer, and hands back both answers as strings the caller
+//! owns, to be given back to [`aoc_answers_free`] once read. Every day also has
+//! an `aoc_day_N` function that does the same. The declarations for C are in
+//! `include/aoc.h`.
+//!
+//! Days use the parameters from the puzzle text, the same as the runner does
+//! without a config. Each part gets the runner's default time budget of a
+//! minute unless [`aoc_set_timeout`] says otherwise.
+//!
+//! Inputs are trusted. A panic comes back as an error, but a stack overflow or
+//! running out of memory aborts the whole process and can't be caught.
+
+#![warn(missing_docs)]
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::panic;
+use std::ptr;
+use std::slice;
+use std::str;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use common::report::{self, PartResult};
+
+/// What solving a day produced. A part that has an answer has it as a
+/// NUL-terminated string, with a bitmap's rows separated by newlines. A part
+/// without one is NULL, and `error` says why. Everything here belongs to the
+/// caller until it's passed to [`aoc_answers_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocAnswers {
+    /// Part 1's answer, or NULL.
+    pub part_1: *mut c_char,
+    /// Part 2's answer, or NULL.
+    pub part_2: *mut c_char,
+    /// NULL when both parts were answered, otherwise what went wrong.
+    pub error: *mut c_char,
+}
+
+impl AocAnswers {
+    fn failed(message: &str) -> AocAnswers {
+        return AocAnswers { part_1: ptr::null_mut(), part_2: ptr::null_mut(), error: owned(message) };
+    }
+}
+
+// a string for C to hold on to, or NULL when it can't be one
+fn owned(text: &str) -> *mut c_char {
+    return CString::new(text).map(CString::into_raw).unwrap_or(ptr::null_mut());
+}
+
+// the input as text, which is all a day can parse
+unsafe fn input<'a>(buffer: *const c_char, length: usize) -> Result<&'a str, String> {
+    if buffer.is_null() {
+        return if length == 0 { Ok("") } else { Err(String::from("the input is NULL but its length isn't 0")) };
+    }
+
+    let bytes = slice::from_raw_parts(buffer as *const u8, length);
+    return str::from_utf8(bytes).map_err(|e| format!("the input isn't UTF-8: {}", e));
+}
+
+// the same default as the runner and the server
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// in nanoseconds, shared by every thread calling in
+static TIMEOUT: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT.as_nanos() as u64);
+
+/// Sets how long each part may take from now on, for every thread. A part
+/// that runs out of time comes back NULL with TIMEOUT in the error. Returns 0,
+/// or -1 and leaves the budget alone when `seconds` isn't a number above 0.
+#[no_mangle]
+pub extern "C" fn aoc_set_timeout(seconds: f64) -> i32 {
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(timeout) if !timeout.is_zero() => {
+            TIMEOUT.store(timeout.as_nanos().min(u128::from(u64::MAX)) as u64, Ordering::Relaxed);
+            return 0;
+        }
+        _ => return -1,
+    }
+}
+
+/*
+ * Nothing may unwind into C, so panics while parsing are caught here and the
+ * parts are run the way the runner runs them, which catches theirs.
+ */
+unsafe fn solve(day: u8, buffer: *const c_char, length: usize, budget: Duration) -> AocAnswers {
+    let parse = match days::find(day) {
+        Some(found) => found.parse,
+        None => return AocAnswers::failed(&format!("there's no day {}", day)),
+    };
+
+    let input = match input(buffer, length) {
+        Ok(input) => input,
+        Err(message) => return AocAnswers::failed(&message),
+    };
+
+    let solution = match panic::catch_unwind(|| parse(input)) {
+        Ok(Ok(solution)) => solution,
+        Ok(Err(e)) => return AocAnswers::failed(&e.to_string()),
+        Err(payload) => return AocAnswers::failed(&format!("day {} {} while parsing", day, report::panicked(&*payload))),
+    };
+
+    let parts = report::solve_parts(day, solution.as_ref(), Some(budget), None);
+
+    let mut errors = vec![];
+    let mut answer = |part: &PartResult| match &part.outcome {
+        Ok(answer) => match CString::new(answer.to_string()) {
+            Ok(answer) => answer.into_raw(),
+            Err(_) => {
+                errors.push(format!("part {}: the answer has a NUL in it", part.part));
+                ptr::null_mut()
+            }
+        },
+        Err(reason) => {
+            errors.push(format!("part {}: {}", part.part, reason));
+            ptr::null_mut()
+        }
+    };
+
+    let part_1 = answer(&parts[0]);
+    let part_2 = answer(&parts[1]);
+    let error = if errors.is_empty() { ptr::null_mut() } else { owned(&errors.join(", ")) };
+
+    return AocAnswers { part_1, part_2, error };
+}
+
+/// Solves day `day` for the `length` bytes of UTF-8 input at `input`. A day
+/// that doesn't exist comes back as an error.
+///
+/// # Safety
+///
+/// `input` must point to at least `length` readable bytes, or be NULL when
+/// `length` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u8, input: *const c_char, length: usize) -> AocAnswers {
+    return solve(day, input, length, Duration::from_nanos(TIMEOUT.load(Ordering::Relaxed)));
+}
+
+/// Frees every string in `answers`. NULL ones are skipped, so it's fine to
+/// pass anything [`aoc_solve`] or an `aoc_day_N` function returned.
+///
+/// # Safety
+///
+/// The strings must have come from [`aoc_solve`] or an `aoc_day_N` function
+/// and not been freed already, or be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_answers_free(answers: AocAnswers) {
+    for text in [answers.part_1, answers.part_2, answers.error] {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    }
+}
+
+// every day's function only differs by its number
+macro_rules! day {
+    ($name:ident, $number:expr) => {
+        #[doc = concat!("Solves day ", $number, " for the `length` bytes of UTF-8 input at `input`.")]
+        ///
+        /// # Safety
+        ///
+        /// `input` must point to at least `length` readable bytes, or be NULL
+        /// when `length` is 0.
+        #[no_mangle]
+        pub unsafe extern "C" fn $name(input: *const c_char, length: usize) -> AocAnswers {
+            return aoc_solve($number, input, length);
+        }
+    };
+}
+
+day!(aoc_day_1, 1);
+day!(aoc_day_2, 2);
+day!(aoc_day_3, 3);
+day!(aoc_day_4, 4);
+day!(aoc_day_5, 5);
+day!(aoc_day_6, 6);
+day!(aoc_day_7, 7);
+day!(aoc_day_8, 8);
+day!(aoc_day_9, 9);
+day!(aoc_day_10, 10);
+day!(aoc_day_11, 11);
+day!(aoc_day_12, 12);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    // the answers as Rust strings, freeing them the way C would
+    fn read(answers: AocAnswers) -> (Option<String>, Option<String>, Option<String>) {
+        let text = |text: *mut c_char| match text.is_null() {
+            true => None,
+            false => Some(unsafe { CStr::from_ptr(text) }.to_string_lossy().to_string()),
+        };
+
+        let read = (text(answers.part_1), text(answers.part_2), text(answers.error));
+        unsafe { aoc_answers_free(answers) };
+
+        return read;
+    }
+
+    fn solve_text(function: unsafe extern "C" fn(*const c_char, usize) -> AocAnswers, input: &str) -> (Option<String>, Option<String>, Option<String>) {
+        return read(unsafe { function(input.as_ptr() as *const c_char, input.len()) });
+    }
+
+    #[test]
+    fn solve_works() {
+        assert_eq!(solve_text(aoc_day_1, "+1\n-2\n+3\n+1"), (Some(String::from("3")), Some(String::from("2")), None));
+        assert_eq!(solve_text(aoc_day_8, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2"), (Some(String::from("138")), Some(String::from("66")), None));
+
+        let input = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+        assert_eq!(read(unsafe { aoc_solve(3, input.as_ptr() as *const c_char, input.len()) }), (Some(String::from("4")), Some(String::from("3")), None));
+
+        // only the first two lines count, the length says where the input stops
+        let input = "+1\n-1\n+5\n+7";
+        assert_eq!(read(unsafe { aoc_day_1(input.as_ptr() as *const c_char, 5) }), (Some(String::from("0")), Some(String::from("0")), None));
+    }
+
+    #[test]
+    fn solve_errors() {
+        let (part_1, part_2, error) = solve_text(aoc_day_4, "[1518-11-01 00:05] eats lunch");
+        assert_eq!((part_1, part_2), (None, None));
+        assert!(error.unwrap().contains("falls asleep, wakes up or Guard #<id> begins shift"));
+
+        assert!(read(unsafe { aoc_day_8(ptr::null(), 0) }).2.is_some());
+        assert!(read(unsafe { aoc_day_1(ptr::null(), 3) }).2.unwrap().contains("NULL"));
+
+        let invalid = [0xff_u8, 0xfe];
+        assert!(read(unsafe { aoc_day_1(invalid.as_ptr() as *const c_char, 2) }).2.unwrap().contains("UTF-8"));
+
+        let (part_1, part_2, error) = read(unsafe { aoc_solve(13, ptr::null(), 0) });
+        assert_eq!((part_1, part_2, error), (None, None, Some(String::from("there's no day 13"))));
+
+        // frequencies that never repeat are an error rather than a hang
+        let (part_1, part_2, error) = solve_text(aoc_day_1, "+1\n+1");
+        assert_eq!((part_1, part_2), (Some(String::from("2")), None));
+        assert_eq!(error, Some(String::from("part 2: no frequency is ever reached twice")));
+    }
+
+    #[test]
+    fn solve_times_out() {
+        // this repeats, but only after ten million passes
+        let input = "+10000000\n-9999999";
+        let answers = unsafe { solve(1, input.as_ptr() as *const c_char, input.len(), Duration::from_millis(50)) };
+
+        assert_eq!(read(answers), (Some(String::from("1")), None, Some(String::from("part 2: TIMEOUT"))));
+    }
+
+    #[test]
+    fn set_timeout_errors() {
+        // the budget is shared with the other tests, so only ones that leave it alone
+        for seconds in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(aoc_set_timeout(seconds), -1);
+        }
+        assert_eq!(TIMEOUT.load(Ordering::Relaxed), DEFAULT_TIMEOUT.as_nanos() as u64);
+    }
+}
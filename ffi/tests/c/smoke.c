@@ -0,0 +1,66 @@
+/*
+ * Solves a couple of the puzzle text's examples through the C API and checks
+ * a bad input and a slow one come back as errors. Exits 0 when everything matched.
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void expect(const char *what, const char *actual, const char *expected) {
+    if (expected == NULL && actual == NULL) {
+        return;
+    }
+
+    if (expected == NULL || actual == NULL || strcmp(actual, expected) != 0) {
+        fprintf(stderr, "%s: expected %s, got %s\n", what, expected ? expected : "NULL", actual ? actual : "NULL");
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *frequencies = "+1\n-2\n+3\n+1";
+    aoc_answers answers = aoc_day_1(frequencies, strlen(frequencies));
+    expect("day 1 part 1", answers.part_1, "3");
+    expect("day 1 part 2", answers.part_2, "2");
+    expect("day 1 error", answers.error, NULL);
+    aoc_answers_free(answers);
+
+    const char *license = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+    answers = aoc_solve(8, license, strlen(license));
+    expect("day 8 part 1", answers.part_1, "138");
+    expect("day 8 part 2", answers.part_2, "66");
+    expect("day 8 error", answers.error, NULL);
+    aoc_answers_free(answers);
+
+    answers = aoc_solve(13, license, strlen(license));
+    expect("day 13 error", answers.error, "there's no day 13");
+    aoc_answers_free(answers);
+
+    const char *records = "[1518-11-01 00:05] eats lunch";
+    answers = aoc_day_4(records, strlen(records));
+    expect("day 4 part 1", answers.part_1, NULL);
+    expect("day 4 part 2", answers.part_2, NULL);
+    if (answers.error == NULL) {
+        fprintf(stderr, "day 4: expected an error\n");
+        failures++;
+    }
+    aoc_answers_free(answers);
+
+    /* repeats only after ten million passes, so part 2 runs out of time */
+    if (aoc_set_timeout(0.05) != 0 || aoc_set_timeout(0) != -1) {
+        fprintf(stderr, "aoc_set_timeout: expected 0 then -1\n");
+        failures++;
+    }
+    const char *slow = "+10000000\n-9999999";
+    answers = aoc_day_1(slow, strlen(slow));
+    expect("slow day 1 part 1", answers.part_1, "1");
+    expect("slow day 1 part 2", answers.part_2, NULL);
+    expect("slow day 1 error", answers.error, "part 2: TIMEOUT");
+    aoc_answers_free(answers);
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,39 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// the shared library cargo test just built, which sits next to this test in deps. The copy one
+// up in target/debug is only updated by cargo build, so it can be missing or out of date.
+fn library_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    return exe.parent().unwrap().to_path_buf();
+}
+
+#[test]
+fn c_program_works() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let library = library_dir();
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("smoke");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let compiled = Command::new(&compiler)
+        .arg("-Wall")
+        .arg("-I").arg(manifest.join("include"))
+        .arg(manifest.join("tests/c/smoke.c"))
+        .arg("-o").arg(&program)
+        .arg("-L").arg(&library)
+        .arg(format!("-Wl,-rpath,{}", library.display()))
+        .arg("-laoc_ffi")
+        .status();
+
+    match compiled {
+        Ok(status) => assert!(status.success(), "{} couldn't build tests/c/smoke.c", compiler),
+        Err(e) => {
+            eprintln!("skipping, no C compiler: {}", e);
+            return;
+        }
+    }
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
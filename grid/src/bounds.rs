@@ -0,0 +1,109 @@
+use crate::Point;
+
+// the smallest rectangle holding a set of points, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(min: Point, max: Point) -> Bounds {
+        Bounds { min, max }
+    }
+
+    // None when there are no points to hold
+    pub fn of<I: IntoIterator<Item = Point>>(points: I) -> Option<Bounds> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Bounds::new(first, first);
+
+        for point in points {
+            bounds.include(point);
+        }
+
+        return Some(bounds);
+    }
+
+    // grows the bounds just enough to hold the point
+    pub fn include(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        return point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y;
+    }
+
+    pub fn is_edge(&self, point: Point) -> bool {
+        return self.contains(point)
+            && (point.x == self.min.x || point.x == self.max.x || point.y == self.min.y || point.y == self.max.y);
+    }
+
+    pub fn width(&self) -> usize {
+        return (self.max.x - self.min.x + 1) as usize;
+    }
+
+    pub fn height(&self) -> usize {
+        return (self.max.y - self.min.y + 1) as usize;
+    }
+
+    pub fn area(&self) -> usize {
+        return self.width() * self.height();
+    }
+
+    pub fn expand(&self, margin: i64) -> Bounds {
+        return Bounds::new(self.min - Point::new(margin, margin), self.max + Point::new(margin, margin));
+    }
+
+    // every point inside, a row at a time from the top left
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Bounds { min, max } = *self;
+
+        return (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)));
+    }
+
+    /*
+     * The hook every grid renders through: one character per point, one
+     * string per row. Keeping it on the bounds means the same picture can be
+     * drawn from a dense grid, a sparse one or just a list of points.
+     */
+    pub fn render<F: FnMut(Point) -> char>(&self, mut cell: F) -> Vec<String> {
+        return (self.min.y..=self.max.y)
+            .map(|y| (self.min.x..=self.max.x).map(|x| cell(Point::new(x, y))).collect())
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn of_works() {
+        let bounds = Bounds::of(vec![Point::new(3, -2), Point::new(-6, 10), Point::new(15, 0)]).unwrap();
+
+        assert_eq!(bounds, Bounds::new(Point::new(-6, -2), Point::new(15, 10)));
+        assert_eq!((bounds.width(), bounds.height(), bounds.area()), (22, 13, 286));
+        assert_eq!(Bounds::of(vec![]), None);
+    }
+
+    #[test]
+    fn edges_work() {
+        let bounds = Bounds::new(Point::new(0, 0), Point::new(2, 2));
+
+        assert!(bounds.is_edge(Point::new(0, 1)));
+        assert!(!bounds.is_edge(Point::new(1, 1)));
+        assert!(!bounds.is_edge(Point::new(3, 1)));
+        assert!(bounds.expand(1).contains(Point::new(-1, 3)));
+    }
+
+    #[test]
+    fn points_and_render_work() {
+        let bounds = Bounds::new(Point::new(1, 1), Point::new(2, 3));
+
+        assert_eq!(bounds.points().count(), bounds.area());
+        assert_eq!(bounds.points().nth(2), Some(Point::new(1, 2)));
+        assert_eq!(bounds.render(|point| if point.x == point.y { '#' } else { '.' }), vec!["#.", ".#", ".."]);
+    }
+}
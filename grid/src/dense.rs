@@ -0,0 +1,97 @@
+use crate::{Bounds, Point};
+
+// a value for every point inside fixed bounds, stored a row at a time
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseGrid<T> {
+    bounds: Bounds,
+    cells: Vec<T>,
+}
+
+impl<T> DenseGrid<T> {
+    pub fn from_fn<F: FnMut(Point) -> T>(bounds: Bounds, cell: F) -> DenseGrid<T> {
+        DenseGrid { bounds, cells: bounds.points().map(cell).collect() }
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        return self.bounds;
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+
+        let row = (point.y - self.bounds.min.y) as usize;
+        let column = (point.x - self.bounds.min.x) as usize;
+
+        return Some(row * self.bounds.width() + column);
+    }
+
+    // None outside the bounds
+    pub fn get(&self, point: Point) -> Option<&T> {
+        return self.index(point).map(|index| &self.cells[index]);
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        return self.index(point).map(move |index| &mut self.cells[index]);
+    }
+
+    // every cell of one row, from left to right
+    pub fn row(&self, y: i64) -> Option<&[T]> {
+        let start = self.index(Point::new(self.bounds.min.x, y))?;
+
+        return Some(&self.cells[start..start + self.bounds.width()]);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        return self.bounds.points().zip(self.cells.iter());
+    }
+
+    // the surrounding cells that fall inside the grid
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        return IntoIterator::into_iter(point.surrounding()).filter_map(move |neighbour| self.get(neighbour).map(|cell| (neighbour, cell)));
+    }
+
+    pub fn render<F: FnMut(&T) -> char>(&self, mut cell: F) -> Vec<String> {
+        return self.bounds.render(|point| cell(self.get(point).unwrap()));
+    }
+}
+
+impl<T: Clone> DenseGrid<T> {
+    pub fn new(bounds: Bounds, fill: T) -> DenseGrid<T> {
+        DenseGrid { bounds, cells: vec![fill; bounds.area()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_works() {
+        let mut grid = DenseGrid::new(Bounds::new(Point::new(-1, -1), Point::new(1, 1)), 0);
+        *grid.get_mut(Point::new(-1, 1)).unwrap() = 7;
+
+        assert_eq!(grid.get(Point::new(-1, 1)), Some(&7));
+        assert_eq!(grid.get(Point::new(0, 0)), Some(&0));
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.iter().filter(|&(_, cell)| *cell == 7).count(), 1);
+        assert_eq!(grid.row(1), Some(&[7, 0, 0][..]));
+        assert_eq!(grid.row(2), None);
+    }
+
+    #[test]
+    fn neighbours_work() {
+        let grid = DenseGrid::from_fn(Bounds::new(Point::new(0, 0), Point::new(2, 2)), |point| point.x + point.y);
+
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours(Point::new(0, 0)).map(|(_, cell)| *cell).sum::<i64>(), 4);
+    }
+
+    #[test]
+    fn render_works() {
+        let grid = DenseGrid::from_fn(Bounds::new(Point::new(0, 0), Point::new(2, 1)), |point| point.x == 1);
+
+        assert_eq!(grid.render(|&on| if on { '#' } else { '.' }), vec![".#.", ".#."]);
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::{Bounds, DenseGrid, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    // perceived brightness, for writing greyscale
+    pub fn luminance(&self) -> u8 {
+        return ((299 * u32::from(self.0) + 587 * u32::from(self.1) + 114 * u32::from(self.2)) / 1000) as u8;
+    }
+
+    fn hex(&self) -> String {
+        return format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2);
+    }
+}
+
+// turns a value scaled to 0..=1 into a colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColourMap {
+    // black to white
+    Grey,
+    // black through red and yellow to white, for counts and densities
+    Heat,
+}
+
+impl ColourMap {
+    pub fn at(&self, value: f64) -> Rgb {
+        let value = if value.is_nan() { 0.0 } else { value.clamp(0.0, 1.0) };
+        let channel = |start: f64| ((value - start) * 3.0).clamp(0.0, 1.0) * 255.0;
+
+        match self {
+            ColourMap::Grey => {
+                let grey = (value * 255.0).round() as u8;
+                Rgb(grey, grey, grey)
+            }
+            ColourMap::Heat => Rgb(channel(0.0) as u8, channel(1.0 / 3.0) as u8, channel(2.0 / 3.0) as u8),
+        }
+    }
+
+    // where value falls between low and high
+    pub fn between(&self, value: f64, low: f64, high: f64) -> Rgb {
+        if high <= low {
+            return self.at(1.0);
+        }
+
+        return self.at((value - low) / (high - low));
+    }
+}
+
+// a colour per category (day 6's regions), spread around the colour wheel so neighbours stand apart
+pub fn category(index: usize) -> Rgb {
+    let hue = (index as f64 * 137.508) % 360.0;
+    let sector = hue / 60.0;
+    let rising = ((1.0 - (sector % 2.0 - 1.0).abs()) * 200.0) as u8 + 40;
+    let (high, low) = (240, 40);
+
+    match sector as u32 {
+        0 => Rgb(high, rising, low),
+        1 => Rgb(rising, high, low),
+        2 => Rgb(low, high, rising),
+        3 => Rgb(low, rising, high),
+        4 => Rgb(rising, low, high),
+        _ => Rgb(high, low, rising),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Pgm,
+    Svg,
+}
+
+impl Format {
+    // picked by the file extension
+    pub fn from_path(path: &str) -> Result<Format, String> {
+        let extension = Path::new(path).extension().and_then(|extension| extension.to_str()).unwrap_or("");
+
+        match extension.to_ascii_lowercase().as_str() {
+            "ppm" => Ok(Format::Ppm),
+            "pgm" => Ok(Format::Pgm),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!("can't tell the image format of {}, use .ppm, .pgm or .svg", path)),
+        }
+    }
+}
+
+/*
+ * A picture with one pixel per grid point, written without any image crates:
+ * PPM and PGM are a short text header followed by raw bytes, and SVG draws a
+ * rectangle per run of same coloured pixels.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn from_fn<F: FnMut(Point) -> Rgb>(bounds: Bounds, pixel: F) -> Image {
+        Image { width: bounds.width(), height: bounds.height(), pixels: bounds.points().map(pixel).collect() }
+    }
+
+    pub fn from_grid<T, F: FnMut(&T) -> Rgb>(grid: &DenseGrid<T>, mut pixel: F) -> Image {
+        return Image::from_fn(grid.bounds(), |point| pixel(grid.get(point).unwrap()));
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        return self.pixels[y * self.width + x];
+    }
+
+    // every pixel becomes a factor by factor square, for pictures that would otherwise be tiny
+    pub fn scale(&self, factor: usize) -> Image {
+        let (width, height) = (self.width * factor, self.height * factor);
+        let pixels = (0..width * height).map(|index| self.pixel(index % width / factor, index / width / factor)).collect();
+
+        return Image { width, height, pixels };
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flat_map(|pixel| vec![pixel.0, pixel.1, pixel.2]));
+
+        return bytes;
+    }
+
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut bytes = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().map(|pixel| pixel.luminance()));
+
+        return bytes;
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" shape-rendering=\"crispEdges\">\n",
+            self.width, self.height
+        );
+
+        for y in 0..self.height {
+            let mut x = 0;
+
+            while x < self.width {
+                let colour = self.pixel(x, y);
+                let run = (x..self.width).take_while(|&next| self.pixel(next, y) == colour).count();
+
+                let _ = writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>", x, y, run, colour.hex());
+                x += run;
+            }
+        }
+
+        svg.push_str("</svg>\n");
+
+        return svg;
+    }
+
+    pub fn encode(&self, format: Format) -> Vec<u8> {
+        match format {
+            Format::Ppm => self.to_ppm(),
+            Format::Pgm => self.to_pgm(),
+            Format::Svg => self.to_svg().into_bytes(),
+        }
+    }
+
+    // in the format the path's extension asks for
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let format = Format::from_path(path)?;
+
+        return fs::write(path, self.encode(format)).map_err(|e| format!("could not write {}: {}", path, e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let bounds = Bounds::new(Point::new(0, 0), Point::new(2, 1));
+        return Image::from_fn(bounds, |point| if point.x == 2 { Rgb::WHITE } else { Rgb(255, 0, 0) });
+    }
+
+    #[test]
+    fn ppm_and_pgm_work() {
+        let image = checkerboard();
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 3 * 6);
+        assert_eq!(&ppm[11..17], &[255, 0, 0, 255, 0, 0]);
+
+        let pgm = image.to_pgm();
+        assert!(pgm.starts_with(b"P5\n3 2\n255\n"));
+        assert_eq!(&pgm[11..14], &[76, 76, 255]);
+    }
+
+    #[test]
+    fn svg_merges_runs() {
+        let svg = checkerboard().to_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"3\" height=\"2\""));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains("<rect x=\"0\" y=\"1\" width=\"2\" height=\"1\" fill=\"#ff0000\"/>"));
+    }
+
+    #[test]
+    fn scale_works() {
+        let image = checkerboard().scale(2);
+
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.pixel(3, 3), Rgb(255, 0, 0));
+        assert_eq!(image.pixel(4, 0), Rgb::WHITE);
+    }
+
+    #[test]
+    fn colour_maps_work() {
+        assert_eq!(ColourMap::Grey.at(0.0), Rgb::BLACK);
+        assert_eq!(ColourMap::Heat.at(1.0), Rgb::WHITE);
+        assert_eq!(ColourMap::Heat.at(1.0 / 3.0), Rgb(255, 0, 0));
+        assert_eq!(ColourMap::Grey.between(15.0, 10.0, 20.0), Rgb(128, 128, 128));
+        assert_ne!(category(0), category(1));
+    }
+
+    #[test]
+    fn format_from_path_works() {
+        assert_eq!(Format::from_path("fabric.PPM"), Ok(Format::Ppm));
+        assert_eq!(Format::from_path("out/regions.svg"), Ok(Format::Svg));
+        assert!(Format::from_path("stars.png").is_err());
+    }
+}
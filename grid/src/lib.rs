@@ -0,0 +1,16 @@
+/*
+ * Two dimensional grids for the days that lay things out on a plane. Every
+ * coordinate is signed so points can wander off to the left or above the
+ * origin (day 10's stars start all over the place).
+ */
+mod bounds;
+mod dense;
+pub mod image;
+mod point;
+mod sparse;
+
+pub use bounds::Bounds;
+pub use dense::DenseGrid;
+pub use image::Image;
+pub use point::Point;
+pub use sparse::SparseGrid;
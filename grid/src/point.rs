@@ -0,0 +1,102 @@
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+// x grows to the right and y grows downwards, the way the puzzles draw them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(&self, other: Point) -> i64 {
+        return (self.x - other.x).abs() + (self.y - other.y).abs();
+    }
+
+    // up, left, right and down
+    pub fn neighbours(&self) -> [Point; 4] {
+        return [
+            Point::new(self.x, self.y - 1),
+            Point::new(self.x - 1, self.y),
+            Point::new(self.x + 1, self.y),
+            Point::new(self.x, self.y + 1),
+        ];
+    }
+
+    // the neighbours plus the four diagonals, in reading order
+    pub fn surrounding(&self) -> [Point; 8] {
+        return [
+            Point::new(self.x - 1, self.y - 1),
+            Point::new(self.x, self.y - 1),
+            Point::new(self.x + 1, self.y - 1),
+            Point::new(self.x - 1, self.y),
+            Point::new(self.x + 1, self.y),
+            Point::new(self.x - 1, self.y + 1),
+            Point::new(self.x, self.y + 1),
+            Point::new(self.x + 1, self.y + 1),
+        ];
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        return Point::new(self.x + other.x, self.y + other.y);
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        return Point::new(self.x - other.x, self.y - other.y);
+    }
+}
+
+// scaling a velocity by a number of seconds
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, times: i64) -> Point {
+        return Point::new(self.x * times, self.y * times);
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manhattan_works() {
+        assert_eq!(Point::new(1, 1).manhattan(Point::new(8, 3)), 9);
+        assert_eq!(Point::new(-2, 5).manhattan(Point::new(-2, 5)), 0);
+    }
+
+    #[test]
+    fn neighbours_work() {
+        let point = Point::new(0, 0);
+
+        assert_eq!(point.neighbours().len(), 4);
+        assert!(point.neighbours().iter().all(|neighbour| neighbour.manhattan(point) == 1));
+        assert_eq!(point.surrounding()[0], Point::new(-1, -1));
+        assert!(!point.surrounding().contains(&point));
+    }
+
+    #[test]
+    fn arithmetic_works() {
+        assert_eq!(Point::new(9, 1) + Point::new(0, 2) * 3, Point::new(9, 7));
+        assert_eq!(Point::new(9, 1) - Point::new(10, 0), Point::new(-1, 1));
+        assert_eq!(Point::new(33, 45).to_string(), "33,45");
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use crate::{Bounds, Point};
+
+// values at only some points of an unbounded plane, with the bounds of those tracked as they go in
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new(), bounds: None }
+    }
+
+    // None until something is inserted
+    pub fn bounds(&self) -> Option<Bounds> {
+        return self.bounds;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.cells.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.cells.is_empty();
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        return self.cells.contains_key(&point);
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        return self.cells.get(&point);
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        return self.cells.get_mut(&point);
+    }
+
+    fn track(&mut self, point: Point) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(point),
+            None => self.bounds = Some(Bounds::new(point, point)),
+        }
+    }
+
+    // the value that was there before, if any
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.track(point);
+        return self.cells.insert(point, value);
+    }
+
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&mut self, point: Point, value: F) -> &mut T {
+        self.track(point);
+        return self.cells.entry(point).or_insert_with(value);
+    }
+
+    // in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        return self.cells.iter().map(|(point, value)| (*point, value));
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        return self.cells.values();
+    }
+
+    // the surrounding points that hold a value
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        return IntoIterator::into_iter(point.surrounding()).filter_map(move |neighbour| self.get(neighbour).map(|cell| (neighbour, cell)));
+    }
+
+    // everything inside the bounds, with None for the points that hold nothing
+    pub fn render<F: FnMut(Option<&T>) -> char>(&self, mut cell: F) -> Vec<String> {
+        match self.bounds {
+            Some(bounds) => bounds.render(|point| cell(self.get(point))),
+            None => vec![],
+        }
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        return SparseGrid::new();
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(cells: I) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+
+        for (point, value) in cells {
+            grid.insert(point, value);
+        }
+
+        return grid;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(3, -2), 'a');
+        grid.insert(Point::new(-6, 10), 'b');
+        grid.get_or_insert_with(Point::new(15, 0), || 'c');
+
+        assert_eq!(grid.bounds(), Some(Bounds::new(Point::new(-6, -2), Point::new(15, 10))));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.insert(Point::new(3, -2), 'd'), Some('a'));
+    }
+
+    #[test]
+    fn neighbours_work() {
+        let grid: SparseGrid<()> = vec![(Point::new(0, 0), ()), (Point::new(1, 1), ()), (Point::new(3, 3), ())].into_iter().collect();
+
+        assert_eq!(grid.neighbours(Point::new(0, 0)).count(), 1);
+        assert_eq!(grid.neighbours(Point::new(3, 3)).count(), 0);
+    }
+
+    #[test]
+    fn render_works() {
+        let grid: SparseGrid<()> = vec![(Point::new(0, 0), ()), (Point::new(2, 1), ())].into_iter().collect();
+
+        assert_eq!(grid.render(|cell| if cell.is_some() { '#' } else { '.' }), vec!["#..", "..#"]);
+        assert!(SparseGrid::<()>::new().render(|_| '#').is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+pub struct ServerArgs {
+    // host:port to listen on, port 0 picks any free one
+    pub address: String,
+    // the biggest input body accepted, in bytes
+    pub max_body: usize,
+    // how long a part may run before it's answered with a TIMEOUT
+    pub timeout: Duration,
+    // how many connections are served at once, the rest wait their turn
+    pub connections: usize,
+}
+
+// only reachable from this machine unless asked otherwise
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8018";
+
+// the biggest real input is 50KB, so this leaves plenty of room for generated ones
+pub const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// each one can be solving on a thread with a big stack, see routes::SOLVE_STACK
+pub const DEFAULT_CONNECTIONS: usize = 16;
+
+impl ServerArgs {
+    pub fn parse(args: &[String]) -> Result<ServerArgs, String> {
+        let mut server_args = ServerArgs { address: String::from(DEFAULT_ADDRESS), max_body: DEFAULT_MAX_BODY, timeout: DEFAULT_TIMEOUT, connections: DEFAULT_CONNECTIONS };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--address" => match args.next() {
+                    Some(address) => server_args.address = address.clone(),
+                    None => return Err(String::from("--address needs a host:port")),
+                },
+                "--max-body" => match args.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(bytes)) if bytes > 0 => server_args.max_body = bytes,
+                    _ => return Err(String::from("--max-body needs a number of bytes greater than 0")),
+                },
+                "--timeout" => match args.next().map(|value| value.parse::<f64>()) {
+                    Some(Ok(seconds)) if seconds > 0.0 && seconds.is_finite() => {
+                        server_args.timeout = Duration::from_secs_f64(seconds)
+                    }
+                    _ => return Err(String::from("--timeout needs a number of seconds greater than 0")),
+                },
+                "--connections" => match args.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(count)) if count > 0 => server_args.connections = count,
+                    _ => return Err(String::from("--connections needs a number greater than 0")),
+                },
+                _ => return Err(format!("unknown argument: {}", arg)),
+            }
+        }
+
+        return Ok(server_args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        return args.iter().map(|arg| arg.to_string()).collect();
+    }
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(
+            ServerArgs::parse(&args(&[])),
+            Ok(ServerArgs { address: String::from("127.0.0.1:8018"), max_body: 1024 * 1024, timeout: Duration::from_secs(60), connections: 16 })
+        );
+        assert_eq!(
+            ServerArgs::parse(&args(&["--address", "0.0.0.0:80", "--max-body", "100", "--timeout", "0.5", "--connections", "2"])),
+            Ok(ServerArgs { address: String::from("0.0.0.0:80"), max_body: 100, timeout: Duration::from_millis(500), connections: 2 })
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(ServerArgs::parse(&args(&["--address"])).is_err());
+        assert!(ServerArgs::parse(&args(&["--max-body", "0"])).is_err());
+        assert!(ServerArgs::parse(&args(&["--timeout", "soon"])).is_err());
+        assert!(ServerArgs::parse(&args(&["--connections", "0"])).is_err());
+        assert!(ServerArgs::parse(&args(&["--port", "80"])).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use std::io::{self, BufRead, Read, Write};
+
+use common::json;
+
+// the request line and headers together, the body has its own limit
+pub const MAX_HEAD: u64 = 8 * 1024;
+
+// what a client that sent `Expect: 100-continue` waits for before the body
+pub const CONTINUE: &[u8] = b"HTTP/1.1 100 Continue\r\n\r\n";
+
+/*
+ * Everything before the body. The body isn't read along with the head so its
+ * length can be checked first, without reading a byte of an oversized one.
+ */
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // everything after the ?, which may be empty
+    pub query: String,
+    pub length: usize,
+    pub expects_continue: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl Response {
+    // the body must already be JSON
+    pub fn json(status: u16, body: String) -> Response {
+        return Response { status, headers: vec![], body: format!("{}\n", body) };
+    }
+
+    pub fn error(status: u16, message: &str) -> Response {
+        return Response::json(status, json::object(&[("error", json::string(message))]));
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Response {
+        self.headers.push((name, value.to_string()));
+        return self;
+    }
+
+    // every response closes the connection, so there's no keep-alive to get wrong
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        head.push_str("Content-Type: application/json\r\n");
+        head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        head.push_str("Connection: close\r\n");
+
+        for (name, value) in self.headers.iter() {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+
+        head.push_str("\r\n");
+        writer.write_all(head.as_bytes())?;
+        writer.write_all(self.body.as_bytes())?;
+
+        return writer.flush();
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        505 => "HTTP Version Not Supported",
+        _ => "Unknown",
+    }
+}
+
+// a socket's read timeout shows up as either of these depending on the platform
+fn timed_out(e: &io::Error) -> bool {
+    return e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut;
+}
+
+// the request line and headers, failing with the response to send instead
+pub fn read_head<R: BufRead>(reader: &mut R) -> Result<Request, Response> {
+    let mut head = reader.take(MAX_HEAD);
+
+    let request_line = read_line(&mut head)?;
+    let (method, target, version) = match request_line.split(' ').collect::<Vec<&str>>().as_slice() {
+        [method, target, version] if !method.is_empty() && target.starts_with('/') => (*method, *target, *version),
+        _ => return Err(Response::error(400, "expected a request line like POST /day/7/part/2 HTTP/1.1")),
+    };
+
+    if version != "HTTP/1.1" && version != "HTTP/1.0" {
+        return Err(Response::error(505, &format!("only HTTP/1.1 is supported, not {}", version)));
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut length = None;
+    let mut expects_continue = false;
+
+    loop {
+        let line = read_line(&mut head)?;
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = match line.split_once(':') {
+            Some((name, value)) => (name.to_ascii_lowercase(), value.trim()),
+            None => return Err(Response::error(400, &format!("expected a header like Name: value, got {}", line))),
+        };
+
+        match name.as_str() {
+            // the same length twice is allowed, two different ones can't both be right
+            "content-length" => match value.parse::<usize>() {
+                Ok(value) if length.is_none_or(|length| length == value) => length = Some(value),
+                _ => return Err(Response::error(400, &format!("invalid Content-Length: {}", value))),
+            },
+            "transfer-encoding" => return Err(Response::error(501, "chunked bodies aren't supported, send a Content-Length")),
+            "expect" => expects_continue = value.eq_ignore_ascii_case("100-continue"),
+            _ => {}
+        }
+    }
+
+    if method == "POST" && length.is_none() {
+        return Err(Response::error(411, "a POST needs a Content-Length"));
+    }
+
+    return Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        length: length.unwrap_or(0),
+        expects_continue,
+    });
+}
+
+// one line of the head without its line ending
+fn read_line<R: BufRead>(head: &mut io::Take<R>) -> Result<String, Response> {
+    let mut line = vec![];
+
+    match head.read_until(b'\n', &mut line) {
+        Ok(_) if line.ends_with(b"\n") => {}
+        Ok(_) if head.limit() == 0 => {
+            return Err(Response::error(431, &format!("the request line and headers are over {} bytes", MAX_HEAD)));
+        }
+        Ok(_) => return Err(Response::error(400, "the request ended inside its headers")),
+        Err(e) if timed_out(&e) => return Err(Response::error(408, "the request took too long to arrive")),
+        Err(e) => return Err(Response::error(400, &e.to_string())),
+    }
+
+    line.pop();
+    if line.ends_with(b"\r") {
+        line.pop();
+    }
+
+    return String::from_utf8(line).map_err(|_| Response::error(400, "the headers aren't UTF-8"));
+}
+
+pub fn read_body<R: Read>(reader: &mut R, length: usize) -> Result<Vec<u8>, Response> {
+    let mut body = vec![0; length];
+
+    match reader.read_exact(&mut body) {
+        Ok(()) => Ok(body),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Err(Response::error(400, "the body is shorter than its Content-Length")),
+        Err(e) if timed_out(&e) => Err(Response::error(408, "the body took too long to arrive")),
+        Err(e) => Err(Response::error(400, &e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn head(text: &str) -> Result<Request, u16> {
+        return read_head(&mut text.as_bytes()).map_err(|response| response.status);
+    }
+
+    #[test]
+    fn read_head_works() {
+        let request = head("POST /day/7/part/2?workers=2 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 12\r\n\r\nStep C must").unwrap();
+        assert_eq!(
+            request,
+            Request {
+                method: String::from("POST"),
+                path: String::from("/day/7/part/2"),
+                query: String::from("workers=2"),
+                length: 12,
+                expects_continue: false,
+            }
+        );
+
+        let request = head("GET / HTTP/1.0\n\n").unwrap();
+        assert_eq!((request.path.as_str(), request.length), ("/", 0));
+
+        let request = head("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 4\r\nExpect: 100-continue\r\n\r\n").unwrap();
+        assert!(request.expects_continue);
+    }
+
+    #[test]
+    fn read_head_errors() {
+        assert_eq!(head("POST /day/1/part/1\r\n\r\n"), Err(400));
+        assert_eq!(head("POST /day/1/part/1 HTTP/2\r\n\r\n"), Err(505));
+        assert_eq!(head("POST /day/1/part/1 HTTP/1.1\r\nHost localhost\r\n\r\n"), Err(400));
+        assert_eq!(head("POST /day/1/part/1 HTTP/1.1\r\nHost: localhost\r\n"), Err(400));
+        assert_eq!(head("POST /day/1/part/1 HTTP/1.1\r\n\r\n"), Err(411));
+        assert_eq!(head("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: -1\r\n\r\n"), Err(400));
+        assert_eq!(head("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2\r\n\r\n"), Err(400));
+        assert_eq!(head("POST /day/1/part/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n"), Err(501));
+
+        let huge = format!("POST /day/1/part/1 HTTP/1.1\r\nCookie: {}\r\n\r\n", "a".repeat(MAX_HEAD as usize));
+        assert_eq!(head(&huge), Err(431));
+    }
+
+    #[test]
+    fn read_body_works() {
+        assert_eq!(read_body(&mut "+1\n-2".as_bytes(), 5), Ok(b"+1\n-2".to_vec()));
+        assert_eq!(read_body(&mut "+1\n-2".as_bytes(), 2), Ok(b"+1".to_vec()));
+        assert_eq!(read_body(&mut "+1".as_bytes(), 5).err().unwrap().status, 400);
+    }
+
+    #[test]
+    fn write_to_works() {
+        let mut written = vec![];
+        Response::error(405, "only POST").with_header("Allow", "POST").write_to(&mut written).unwrap();
+
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "HTTP/1.1 405 Method Not Allowed\r\nContent-Type: application/json\r\nContent-Length: 23\r\nConnection: close\r\nAllow: POST\r\n\r\n{\"error\": \"only POST\"}\n"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+mod args;
+mod http;
+mod routes;
+mod slots;
+
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::process;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use args::ServerArgs;
+use http::Response;
+use slots::{Slot, Slots};
+
+const USAGE: &str = "Usage: aoc-server [--address <host:port>] [--max-body <bytes>] [--timeout <secs>] [--connections <count>]
+
+POST a day's input to /day/<day>/part/<1|2> to get that part's answer as JSON.
+Parameters go in the query string, e.g. /day/7/part/2?workers=2&step_offset=0.";
+
+// how long a client gets to send its request, so a slow one can't hold a thread forever
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let server_args = match ServerArgs::parse(&args) {
+        Ok(server_args) => server_args,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+
+    let listener = match TcpListener::bind(&server_args.address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("couldn't listen on {}: {}", server_args.address, e);
+            process::exit(1);
+        }
+    };
+
+    // the address actually bound, which is the only way to find out the port when it was 0
+    match listener.local_addr() {
+        Ok(address) => println!("listening on http://{}", address),
+        Err(_) => println!("listening on http://{}", server_args.address),
+    }
+
+    let slots = Slots::new(server_args.connections);
+    let server_args = Arc::new(server_args);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                // waits here while every slot is busy, so connections can't pile up
+                let slot = slots.take();
+                let server_args = Arc::clone(&server_args);
+                thread::spawn(move || serve(stream, &server_args, slot));
+            }
+            Err(e) => eprintln!("couldn't accept a connection: {}", e),
+        }
+    }
+}
+
+// answers one request and closes the connection, holding on to its slot until the solve is over
+fn serve(stream: TcpStream, server_args: &ServerArgs, _slot: Slot) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut reader = BufReader::new(&stream);
+
+    let (response, worker) = respond(&mut reader, &stream, server_args);
+    match response.write_to(&mut &stream) {
+        Ok(()) => finish(&stream, reader, server_args),
+        Err(e) => eprintln!("couldn't send a response: {}", e),
+    }
+
+    // a solve that ran out of time still counts against --connections until it stops
+    if let Some(worker) = worker {
+        let _ = worker.join();
+    }
+}
+
+/*
+ * Closing with part of the request still unread (an oversized body, say)
+ * resets the connection, which can lose the response on its way out. So the
+ * client gets to see our end close first, and whatever it still sends is
+ * thrown away, up to a limit.
+ */
+fn finish(stream: &TcpStream, reader: BufReader<&TcpStream>, server_args: &ServerArgs) {
+    let _ = stream.shutdown(Shutdown::Write);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+    let _ = io::copy(&mut reader.take(server_args.max_body as u64), &mut io::sink());
+}
+
+// the response, and the thread still solving for it if there is one
+fn respond<R: BufRead>(reader: &mut R, mut stream: &TcpStream, server_args: &ServerArgs) -> (Response, Option<JoinHandle<()>>) {
+    let request = match http::read_head(reader) {
+        Ok(request) => request,
+        Err(response) => return (response, None),
+    };
+
+    // checked before reading any of it, so a huge body is never held in memory
+    if request.length > server_args.max_body {
+        return (Response::error(413, &format!("the input is over the {} byte limit", server_args.max_body)), None);
+    }
+
+    if request.expects_continue && stream.write_all(http::CONTINUE).is_err() {
+        return (Response::error(400, "couldn't tell the client to send its body"), None);
+    }
+
+    let body = match http::read_body(reader, request.length) {
+        Ok(body) => body,
+        Err(response) => return (response, None),
+    };
+
+    let (response, worker) = routes::handle(&request, &body, server_args.timeout);
+    eprintln!("{} {} {}", request.method, request.path, response.status);
+
+    return (response, worker);
+}
@@ -0,0 +1,260 @@
+use std::panic;
+use std::str;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use common::report::{self, PartResult, PANICKED, TIMEOUT};
+use common::{json, Params};
+use days::Parse;
+
+use crate::http::{Request, Response};
+
+// a new thread only gets 2MiB, which is a thin margin for a solver working through a big input
+const SOLVE_STACK: usize = 64 * 1024 * 1024;
+
+/*
+ * The only route is POST /day/<day>/part/<part>, with the input as the body and
+ * any parameters in the query string. An answer comes back as the same object
+ * `aoc run --json` prints for a part, anything else as {"error": ...}.
+ *
+ * The solving happens on a thread of its own, which comes back too when there
+ * was one. It can still be going after a timeout has been sent.
+ */
+pub fn handle(request: &Request, body: &[u8], timeout: Duration) -> (Response, Option<JoinHandle<()>>) {
+    let (number, part, parse, params, input) = match route(request, body) {
+        Ok(route) => route,
+        Err(response) => return (response, None),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::Builder::new()
+        .name(format!("day {} part {}", number, part))
+        .stack_size(SOLVE_STACK)
+        .spawn(move || {
+            let _ = sender.send(solve(number, part, parse, &input, &params, timeout));
+        });
+
+    let worker = match worker {
+        Ok(worker) => worker,
+        Err(e) => return (Response::error(503, &format!("couldn't start solving: {}", e)), None),
+    };
+
+    /*
+     * Most days stop once they notice they're out of time, but not all of them
+     * check. So the answer is only waited on for as long as the budget, and a
+     * part that's still going is told to the client as a timeout.
+     */
+    let response = match receiver.recv_timeout(timeout) {
+        Ok(response) => response,
+        Err(RecvTimeoutError::Timeout) => Response::json(503, PartResult::failed(number, part, TIMEOUT).to_json()),
+        Err(RecvTimeoutError::Disconnected) => Response::error(500, &format!("day {} stopped without an answer", number)),
+    };
+
+    return (response, Some(worker));
+}
+
+// which day and part to solve with what, or the response saying why not
+fn route(request: &Request, body: &[u8]) -> Result<(u8, u8, Parse, Params, String), Response> {
+    let (day, part) = match request.path.split('/').collect::<Vec<&str>>().as_slice() {
+        ["", "day", day, "part", part] => (day.to_string(), part.to_string()),
+        _ => return Err(Response::error(404, &format!("nothing at {}, POST an input to /day/<day>/part/<1|2>", request.path))),
+    };
+
+    if request.method != "POST" {
+        return Err(Response::error(405, "POST the input to solve it").with_header("Allow", "POST"));
+    }
+
+    let (number, parse) = match day.parse::<u8>().ok().and_then(|number| Some((number, days::find(number)?.parse))) {
+        Some(found) => found,
+        None => return Err(Response::error(404, &format!("there's no day {}", day))),
+    };
+
+    let part = match part.as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => return Err(Response::error(404, &format!("day {} only has parts 1 and 2", number))),
+    };
+
+    let params = match query_params(&request.query) {
+        Ok(params) => params,
+        Err(message) => return Err(Response::error(400, &message)),
+    };
+
+    let input = match str::from_utf8(body) {
+        Ok(input) => input,
+        Err(e) => return Err(Response::error(400, &format!("the input isn't UTF-8: {}", e))),
+    };
+
+    return Ok((number, part, parse, params, input.to_string()));
+}
+
+// parses the input and solves the part, on the thread handle started
+fn solve(number: u8, part: u8, parse: Parse, input: &str, params: &Params, timeout: Duration) -> Response {
+    let mut solution = match panic::catch_unwind(|| parse(input)) {
+        Ok(Ok(solution)) => solution,
+        Ok(Err(e)) => {
+            let fields = [("error", json::string(&e.to_string())), ("line", e.line.to_string()), ("column", e.column.to_string())];
+            return Response::json(422, json::object(&fields));
+        }
+        Err(payload) => return Response::error(500, &format!("day {} {} while parsing", number, report::panicked(&*payload))),
+    };
+
+    // a few bytes of input or query can still ask for more than any budget covers, which would
+    // carry on well past the timeout since not every day checks for it
+    let checked = common::configure(solution.as_mut(), params).and_then(|_| solution.check_size());
+    if let Err(message) = checked {
+        return Response::error(400, &format!("day {}: {}", number, message));
+    }
+
+    let result = PartResult::run(number, part, Some(timeout), || match part {
+        1 => solution.part_1(),
+        _ => solution.part_2(),
+    });
+
+    let status = match &result.outcome {
+        Ok(_) => 200,
+        Err(reason) if reason == TIMEOUT => 503,
+        Err(reason) if reason.starts_with(PANICKED) => 500,
+        // the input has no answer for this part
+        Err(_) => 422,
+    };
+
+    return Response::json(status, result.to_json());
+}
+
+// key=value pairs separated by &, the same parameters --set takes
+fn query_params(query: &str) -> Result<Params, String> {
+    let mut params = Params::default();
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        match pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => params.set(key, value),
+            _ => return Err(format!("expected key=value in the query string, got {}", pair)),
+        }
+    }
+
+    return Ok(params);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    const STEPS: &str = "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    fn post(target: &str, body: &str) -> Response {
+        return post_within(target, body, Duration::from_secs(60));
+    }
+
+    fn request(target: &str, body: &str) -> Request {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        return Request {
+            method: String::from("POST"),
+            path: path.to_string(),
+            query: query.to_string(),
+            length: body.len(),
+            expects_continue: false,
+        };
+    }
+
+    // waits for the solve to finish as well, so nothing's left running
+    fn post_within(target: &str, body: &str, timeout: Duration) -> Response {
+        let (response, worker) = handle(&request(target, body), body.as_bytes(), timeout);
+        if let Some(worker) = worker {
+            worker.join().unwrap();
+        }
+
+        return response;
+    }
+
+    #[test]
+    fn handle_works() {
+        let response = post("/day/7/part/1", STEPS);
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with("{\"day\": 7, \"part\": 1, \"answer\": \"CABDFE\", \"answer_type\": \"text\""));
+
+        let response = post("/day/7/part/2?workers=2&step_offset=0", STEPS);
+        assert!(response.body.starts_with("{\"day\": 7, \"part\": 2, \"answer\": 15,"));
+
+        assert_eq!(post("/day/8/part/2", "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2").status, 200);
+    }
+
+    #[test]
+    fn handle_errors() {
+        assert_eq!(post("/", "").status, 404);
+        assert_eq!(post("/day/7/part/2/", STEPS).status, 404);
+        assert_eq!(post("/day/13/part/1", STEPS).status, 404);
+        assert_eq!(post("/day/7/part/3", STEPS).status, 404);
+        assert_eq!(post("/day/7/part/2?workers", STEPS).status, 400);
+        assert_eq!(post("/day/7/part/2?wrokers=2", STEPS).status, 400);
+
+        let response = post("/day/8/part/1", "0 1 x");
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("\"line\": 1, \"column\": 5"));
+
+        let request = Request { method: String::from("GET"), path: String::from("/day/7/part/1"), query: String::new(), length: 0, expects_continue: false };
+        let (response, worker) = handle(&request, b"", Duration::from_secs(60));
+        assert!(worker.is_none());
+        assert_eq!((response.status, response.headers), (405, vec![("Allow", String::from("POST"))]));
+    }
+
+    #[test]
+    fn handle_hard_inputs() {
+        // a tree 100,000 nodes deep, each with one child and a metadata entry pointing at it
+        let depth = 100_000;
+        let tree = format!("{}0 1 7{}", "1 1 ".repeat(depth), " 1".repeat(depth));
+        assert!(post("/day/8/part/1", &tree).body.contains(&format!("\"answer\": {},", depth + 7)));
+        assert!(post("/day/8/part/2", &tree).body.contains("\"answer\": 7,"));
+
+        // frequencies that never repeat have no answer
+        let response = post("/day/1/part/2", "+1\n+1");
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("no frequency is ever reached twice"));
+    }
+
+    #[test]
+    fn handle_too_big() {
+        // 19 bytes asking for a grid of ten billion locations
+        let response = post("/day/6/part/1", "0, 0\n100000, 100000");
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("too much"));
+
+        assert_eq!(post("/day/11/part/2?grid_size=100000", "18").status, 400);
+        assert_eq!(post(&format!("/day/9/part/2?marbles={}", usize::MAX), "9 players; last marble is worth 25 points").status, 400);
+        assert_eq!(post("/day/12/part/1?part_1_generations=10000000000", "initial state: #\n\n..#.. => #").status, 400);
+    }
+
+    #[test]
+    fn handle_times_out() {
+        // day 11 never checks for cancellation, and its whole grid takes a while to search
+        let start = Instant::now();
+        let (response, worker) = handle(&request("/day/11/part/2", "18"), b"18", Duration::from_millis(100));
+        let worker = worker.unwrap();
+
+        // the answer didn't wait for the solve
+        assert!(!worker.is_finished());
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(response.status, 503);
+        assert!(response.body.contains("\"error\": \"TIMEOUT\""));
+
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn query_params_works() {
+        let params = query_params("workers=2&&step_offset=0").unwrap();
+        assert_eq!(params.pairs(), vec![("step_offset", "0"), ("workers", "2")]);
+
+        assert!(query_params("").unwrap().is_empty());
+        assert!(query_params("=2").is_err());
+    }
+}
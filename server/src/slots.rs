@@ -0,0 +1,72 @@
+use std::sync::{Arc, Condvar, Mutex};
+
+/*
+ * How many connections can be served at once. Taking a slot waits for one to
+ * be free, so while they're all busy new connections queue up in the listen
+ * backlog instead of each getting a thread.
+ */
+pub struct Slots {
+    free: Mutex<usize>,
+    freed: Condvar,
+}
+
+// one connection's place, given back when it's dropped
+pub struct Slot {
+    slots: Arc<Slots>,
+}
+
+impl Slots {
+    pub fn new(count: usize) -> Arc<Slots> {
+        return Arc::new(Slots { free: Mutex::new(count), freed: Condvar::new() });
+    }
+
+    pub fn take(self: &Arc<Self>) -> Slot {
+        let mut free = self.free.lock().unwrap();
+        while *free == 0 {
+            free = self.freed.wait(free).unwrap();
+        }
+
+        *free -= 1;
+        return Slot { slots: Arc::clone(self) };
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        *self.slots.free.lock().unwrap() += 1;
+        self.slots.freed.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn take_works() {
+        let slots = Slots::new(2);
+
+        let first = slots.take();
+        let second = slots.take();
+
+        // a third has to wait until one of the others is done
+        let (sender, receiver) = mpsc::channel();
+        let waiting = Arc::clone(&slots);
+        let third = thread::spawn(move || {
+            let _slot = waiting.take();
+            sender.send(()).unwrap();
+        });
+
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(first);
+        assert!(receiver.recv_timeout(Duration::from_secs(10)).is_ok());
+        third.join().unwrap();
+
+        // both are back once everything's been dropped
+        drop(second);
+        let _both = (slots.take(), slots.take());
+    }
+}
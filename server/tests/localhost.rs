@@ -0,0 +1,127 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+// stops the server however the test ends
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    fn start(args: &[&str]) -> Server {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_aoc-server"))
+            .args(["--address", "127.0.0.1:0"])
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+        let address = line.trim().trim_start_matches("listening on http://").to_string();
+
+        return Server { child, address };
+    }
+
+    fn send_raw(&self, request: &[u8]) -> String {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        stream.write_all(request).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        return response;
+    }
+
+    // the status and body of the response to a raw request
+    fn send(&self, request: &[u8]) -> (u16, String) {
+        let response = self.send_raw(request);
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+
+        return (status, body);
+    }
+
+    fn post(&self, target: &str, input: &str) -> (u16, String) {
+        let request = format!("POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", target, input.len(), input);
+        return self.send(request.as_bytes());
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn solves_over_http() {
+    let server = Server::start(&[]);
+
+    let steps = "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    let (status, body) = server.post("/day/7/part/2?workers=2&step_offset=0", steps);
+    assert_eq!(status, 200);
+    assert!(body.starts_with("{\"day\": 7, \"part\": 2, \"answer\": 15, \"answer_type\": \"integer\", \"elapsed_ns\": "));
+
+    let (status, body) = server.post("/day/1/part/1", "+1\n-2\n+3\n+1");
+    assert_eq!((status, body.contains("\"answer\": 3,")), (200, true));
+
+    // curl asks before sending anything over a kilobyte
+    let input = "+1\n-1\n".repeat(200);
+    let request = format!("POST /day/1/part/2 HTTP/1.1\r\nContent-Length: {}\r\nExpect: 100-continue\r\n\r\n{}", input.len(), input);
+    let response = server.send_raw(request.as_bytes());
+    assert!(response.starts_with("HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("\"answer\": 0,"));
+
+    // deep enough to overflow a solver that recursed
+    let depth = 100_000;
+    let tree = format!("{}0 1 7{}", "1 1 ".repeat(depth), " 1".repeat(depth));
+    let (status, body) = server.post("/day/8/part/1", &tree);
+    assert_eq!((status, body.contains(&format!("\"answer\": {},", depth + 7))), (200, true));
+}
+
+#[test]
+fn rejects_bad_requests() {
+    let server = Server::start(&["--max-body", "64"]);
+
+    let (status, body) = server.post("/day/1/part/1", &"+1\n".repeat(100));
+    assert_eq!(status, 413);
+    assert_eq!(body, "{\"error\": \"the input is over the 64 byte limit\"}\n");
+
+    assert_eq!(server.send(b"GET /day/1/part/1 HTTP/1.1\r\n\r\n").0, 405);
+    assert_eq!(server.send(b"POST /day/1/part/1 HTTP/1.1\r\n\r\n").0, 411);
+    assert_eq!(server.post("/day/26/part/1", "+1").0, 404);
+    assert_eq!(server.post("/day/4/part/1", "[1518-11-01 00:05] eats lunch").0, 422);
+    assert_eq!(server.post("/day/1/part/2", "+1\n+1").0, 422);
+
+    // the server is still there after all of that
+    assert_eq!(server.post("/day/1/part/1", "+1\n+1").0, 200);
+}
+
+#[test]
+fn times_out_slow_solves() {
+    let server = Server::start(&["--timeout", "0.2", "--connections", "1"]);
+
+    // day 6 doesn't check for cancellation, and this is 70 million distances, which takes a couple of seconds
+    let coordinates: Vec<String> = (0..80).map(|i| format!("{}, {}", i * 12, i * 12)).collect();
+    let start = Instant::now();
+    let (status, body) = server.post("/day/6/part/2?safe_distance=0", &coordinates.join("\n"));
+    assert_eq!((status, body.contains("\"error\": \"TIMEOUT\"")), (503, true));
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // it still has the only connection, so this waits for it to finish
+    let (status, _) = server.post("/day/1/part/1", "+1\n+1");
+    assert_eq!(status, 200);
+    assert!(start.elapsed() > Duration::from_secs(1));
+}